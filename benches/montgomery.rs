@@ -0,0 +1,64 @@
+//! Compares `Montgomery64` against `u128` remainder for modular exponentiation.
+//!
+//! Run with `cargo bench --bench montgomery`.
+
+use algorithm_rs::math::montgomery::Montgomery64;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+const MODULUS: u64 = (1 << 62) - 57;
+const ITERATIONS: u64 = 200_000;
+const ROUNDS: u32 = 5;
+
+fn naive_pow(mut a: u64, mut exp: u64, n: u64) -> u64 {
+    let mut result = 1;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = (result as u128 * a as u128 % n as u128) as u64;
+        }
+        a = (a as u128 * a as u128 % n as u128) as u64;
+        exp >>= 1;
+    }
+
+    result
+}
+
+fn with_u128(n: u64) -> u64 {
+    (2..ITERATIONS + 2).fold(0, |acc, a| acc ^ naive_pow(a, n - 1, n))
+}
+
+fn with_montgomery(n: u64) -> u64 {
+    let mont = Montgomery64::new(n);
+    (2..ITERATIONS + 2).fold(0, |acc, a| acc ^ mont.pow_mod(a, n - 1))
+}
+
+fn measure(name: &str, f: fn(u64) -> u64) -> (u64, Duration) {
+    let mut best = Duration::MAX;
+    let mut result = 0;
+
+    for _ in 0..ROUNDS {
+        let start = Instant::now();
+        result = black_box(f(black_box(MODULUS)));
+        best = best.min(start.elapsed());
+    }
+
+    println!(
+        "{:<24} {:>10.3} ms ({} exponentiations)",
+        name,
+        best.as_secs_f64() * 1e3,
+        ITERATIONS
+    );
+
+    (result, best)
+}
+
+fn main() {
+    let (expected, naive) = measure("u128 remainder", with_u128);
+    let (actual, montgomery) = measure("Montgomery64", with_montgomery);
+
+    assert_eq!(expected, actual);
+    println!(
+        "speedup: {:.2}x",
+        naive.as_secs_f64() / montgomery.as_secs_f64()
+    );
+}
@@ -0,0 +1,90 @@
+//! Compares `Scanner` against the usual `read_line` + `split_whitespace` idiom.
+//!
+//! Run with `cargo bench --bench scanner`.
+
+use algorithm_rs::io::Scanner;
+use std::hint::black_box;
+use std::io::{BufRead, BufReader};
+use std::time::{Duration, Instant};
+
+const LINES: usize = 1_000;
+const PER_LINE: usize = 1_000;
+const ROUNDS: u32 = 5;
+
+fn generate_input() -> Vec<u8> {
+    let mut state = 0x2545_f491_4f6c_dd1d_u64;
+    let mut input = String::new();
+
+    for _ in 0..LINES {
+        for j in 0..PER_LINE {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+
+            if j > 0 {
+                input.push(' ');
+            }
+            input.push_str(&(state as i64 >> 3).to_string());
+        }
+        input.push('\n');
+    }
+
+    input.into_bytes()
+}
+
+fn with_scanner(input: &[u8]) -> i64 {
+    let mut scanner = Scanner::new(input).unwrap();
+    let mut sum = 0i64;
+
+    for _ in 0..LINES * PER_LINE {
+        sum = sum.wrapping_add(scanner.read::<i64>().unwrap());
+    }
+
+    sum
+}
+
+fn with_read_line(input: &[u8]) -> i64 {
+    let mut reader = BufReader::new(input);
+    let mut line = String::new();
+    let mut sum = 0i64;
+
+    for _ in 0..LINES {
+        line.clear();
+        reader.read_line(&mut line).unwrap();
+
+        for token in line.split_whitespace() {
+            sum = sum.wrapping_add(token.parse::<i64>().unwrap());
+        }
+    }
+
+    sum
+}
+
+fn measure(name: &str, input: &[u8], f: fn(&[u8]) -> i64) -> i64 {
+    let mut best = Duration::MAX;
+    let mut result = 0;
+
+    for _ in 0..ROUNDS {
+        let start = Instant::now();
+        result = black_box(f(black_box(input)));
+        best = best.min(start.elapsed());
+    }
+
+    println!(
+        "{:<24} {:>10.3} ms ({} tokens)",
+        name,
+        best.as_secs_f64() * 1e3,
+        LINES * PER_LINE
+    );
+
+    result
+}
+
+fn main() {
+    let input = generate_input();
+
+    let expected = measure("read_line + split", &input, with_read_line);
+    let actual = measure("Scanner", &input, with_scanner);
+
+    assert_eq!(expected, actual);
+}
@@ -0,0 +1,858 @@
+//! Inlines the modules of this crate used by a solution into a single file.
+//!
+//! ```text
+//! cargo run --bin bundle -- path/to/main.rs [--crate <dir>] [--output <file>]
+//! ```
+//!
+//! Every `algorithm_rs::...` path in the solution is resolved to a module of
+//! this crate, the modules those reference are collected transitively, and the
+//! result is appended to the solution as a nested `mod algorithm_rs`. Modules
+//! that add `impl` blocks to a collected type are collected as well, since a
+//! solution can call their methods without naming them. Comments, doc
+//! comments and `#[cfg(test)]` items are stripped from the inlined code.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process;
+
+const CRATE_NAME: &str = "algorithm_rs";
+
+/// Keywords that may directly precede a path starting with `::`.
+const KEYWORDS: [&[u8]; 10] = [
+    b"as", b"dyn", b"for", b"impl", b"in", b"let", b"mut", b"return", b"use", b"where",
+];
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum SpanKind {
+    Comment,
+    Literal,
+}
+
+fn is_ident_byte(c: u8) -> bool {
+    c.is_ascii_alphanumeric() || c == b'_'
+}
+
+fn utf8_width(first: u8) -> usize {
+    match first {
+        0xf0..=0xff => 4,
+        0xe0..=0xef => 3,
+        0xc0..=0xdf => 2,
+        _ => 1,
+    }
+}
+
+/// Returns the end of the raw string literal starting at `i`, if there is one.
+fn raw_string_end(src: &[u8], i: usize) -> Option<usize> {
+    let mut j = i + 1;
+    while src.get(j) == Some(&b'#') {
+        j += 1;
+    }
+    if src.get(j) != Some(&b'"') {
+        return None;
+    }
+
+    let hashes = j - i - 1;
+    j += 1;
+    while j < src.len() {
+        if src[j] == b'"'
+            && src[j + 1..]
+                .iter()
+                .take(hashes)
+                .filter(|&&c| c == b'#')
+                .count()
+                == hashes
+        {
+            return Some(j + 1 + hashes);
+        }
+        j += 1;
+    }
+
+    Some(src.len())
+}
+
+/// Returns the end of the character literal starting at `i`, or `None` for a lifetime.
+fn char_literal_end(src: &[u8], i: usize) -> Option<usize> {
+    if *src.get(i + 1)? == b'\\' {
+        let mut j = i + 3;
+        while *src.get(j)? != b'\'' {
+            j += 1;
+        }
+        return Some(j + 1);
+    }
+
+    let width = utf8_width(src[i + 1]);
+    (src.get(i + 1 + width) == Some(&b'\'')).then_some(i + 2 + width)
+}
+
+/// Finds the comments and literals of `src` as half-open byte ranges.
+fn lex(src: &[u8]) -> Vec<(usize, usize, SpanKind)> {
+    let mut spans = Vec::new();
+    let mut i = 0;
+
+    while i < src.len() {
+        let start = i;
+        let prev = if i > 0 { src[i - 1] } else { b' ' };
+
+        let kind = match src[i] {
+            b'/' if src.get(i + 1) == Some(&b'/') => {
+                while i < src.len() && src[i] != b'\n' {
+                    i += 1;
+                }
+                SpanKind::Comment
+            }
+            b'/' if src.get(i + 1) == Some(&b'*') => {
+                let mut depth = 0;
+                while i < src.len() {
+                    if src[i..].starts_with(b"/*") {
+                        depth += 1;
+                        i += 2;
+                    } else if src[i..].starts_with(b"*/") {
+                        depth -= 1;
+                        i += 2;
+                        if depth == 0 {
+                            break;
+                        }
+                    } else {
+                        i += 1;
+                    }
+                }
+                SpanKind::Comment
+            }
+            b'r' if !is_ident_byte(prev)
+                || (prev == b'b' && (i < 2 || !is_ident_byte(src[i - 2]))) =>
+            {
+                match raw_string_end(src, i) {
+                    Some(end) => {
+                        i = end;
+                        SpanKind::Literal
+                    }
+                    None => {
+                        i += 1;
+                        continue;
+                    }
+                }
+            }
+            b'"' => {
+                i += 1;
+                while i < src.len() && src[i] != b'"' {
+                    if src[i] == b'\\' {
+                        i += 1;
+                    }
+                    i += 1;
+                }
+                i += 1;
+                SpanKind::Literal
+            }
+            b'\'' => match char_literal_end(src, i) {
+                Some(end) => {
+                    i = end;
+                    SpanKind::Literal
+                }
+                None => {
+                    i += 1;
+                    continue;
+                }
+            },
+            _ => {
+                i += 1;
+                continue;
+            }
+        };
+
+        i = i.min(src.len());
+        spans.push((start, i, kind));
+    }
+
+    spans
+}
+
+/// Returns a copy of `src` with comments and literals blanked out, keeping byte offsets.
+fn mask(src: &str) -> Vec<u8> {
+    let mut masked = src.as_bytes().to_vec();
+    for (start, end, _) in lex(src.as_bytes()) {
+        for c in &mut masked[start..end] {
+            if *c != b'\n' {
+                *c = b' ';
+            }
+        }
+    }
+
+    masked
+}
+
+/// Removes the byte ranges in `ranges`, which must be sorted and disjoint.
+fn remove_ranges(src: &str, ranges: &[(usize, usize)]) -> String {
+    let mut result = String::with_capacity(src.len());
+    let mut last = 0;
+    for &(start, end) in ranges {
+        result.push_str(&src[last..start]);
+        last = end;
+    }
+    result.push_str(&src[last..]);
+
+    result
+}
+
+/// Returns the end of the item starting at `i`, skipping leading attributes.
+fn item_end(masked: &[u8], mut i: usize) -> usize {
+    let mut depth = 0usize;
+
+    while i < masked.len() {
+        match masked[i] {
+            b'(' | b'[' => depth += 1,
+            b')' | b']' => depth = depth.saturating_sub(1),
+            b';' if depth == 0 => return i + 1,
+            b'{' if depth == 0 => {
+                let mut braces = 0usize;
+                while i < masked.len() {
+                    match masked[i] {
+                        b'{' => braces += 1,
+                        b'}' => {
+                            braces -= 1;
+                            if braces == 0 {
+                                return i + 1;
+                            }
+                        }
+                        _ => {}
+                    }
+                    i += 1;
+                }
+                return masked.len();
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+
+    masked.len()
+}
+
+/// Strips comments, `#[cfg(test)]` items and blank lines from `src`.
+fn strip(src: &str) -> String {
+    let comments: Vec<_> = lex(src.as_bytes())
+        .into_iter()
+        .filter(|&(_, _, kind)| kind == SpanKind::Comment)
+        .map(|(start, end, _)| (start, end))
+        .collect();
+    let src = remove_ranges(src, &comments);
+
+    let masked = mask(&src);
+    let pattern = b"#[cfg(test)]";
+    let mut tests = Vec::new();
+    let mut i = 0;
+    while let Some(offset) = masked[i..]
+        .windows(pattern.len())
+        .position(|w| w == pattern)
+    {
+        let start = i + offset;
+        let end = item_end(&masked, start + pattern.len());
+        tests.push((start, end));
+        i = end;
+    }
+    let src = remove_ranges(&src, &tests);
+
+    let literals = lex(src.as_bytes());
+    let mut result = String::with_capacity(src.len());
+    let mut offset = 0;
+    for line in src.split_inclusive('\n') {
+        let in_literal = literals
+            .iter()
+            .any(|&(start, end, _)| start < offset && offset < end);
+        if in_literal || !line.trim().is_empty() {
+            result.push_str(line.trim_end());
+            result.push('\n');
+        }
+        offset += line.len();
+    }
+
+    result
+}
+
+/// Parses the use tree starting at `i`, returning the paths it names and where it ends.
+fn parse_tree(b: &[u8], mut i: usize) -> (Vec<Vec<String>>, usize) {
+    let skip_whitespace = |mut i: usize| {
+        while i < b.len() && b[i].is_ascii_whitespace() {
+            i += 1;
+        }
+        i
+    };
+
+    i = skip_whitespace(i);
+    if b.get(i) == Some(&b'{') {
+        let mut paths = Vec::new();
+        i += 1;
+        loop {
+            i = skip_whitespace(i);
+            match b.get(i) {
+                None => break,
+                Some(b'}') => {
+                    i += 1;
+                    break;
+                }
+                Some(b',') => i += 1,
+                Some(_) => {
+                    let (subpaths, end) = parse_tree(b, i);
+                    if end == i {
+                        i += 1;
+                    }
+                    paths.extend(subpaths);
+                    i = end.max(i);
+                }
+            }
+        }
+        return (paths, i);
+    }
+
+    let start = i;
+    while i < b.len() && is_ident_byte(b[i]) {
+        i += 1;
+    }
+    if start == i {
+        return (vec![Vec::new()], i);
+    }
+    let ident = String::from_utf8_lossy(&b[start..i]).into_owned();
+
+    let after = skip_whitespace(i);
+    if b[after..].starts_with(b"::") {
+        let (subpaths, end) = parse_tree(b, after + 2);
+        let paths = subpaths
+            .into_iter()
+            .map(|mut path| {
+                path.insert(0, ident.clone());
+                path
+            })
+            .collect();
+        return (paths, end);
+    }
+
+    (vec![vec![ident]], i)
+}
+
+/// Calls `f` with the start offset, length and segments of every path in `masked`.
+fn for_each_path(masked: &[u8], mut f: impl FnMut(usize, usize, Vec<String>)) {
+    let mut i = 0;
+    while i < masked.len() {
+        if !is_ident_byte(masked[i]) || (i > 0 && is_ident_byte(masked[i - 1])) {
+            i += 1;
+            continue;
+        }
+
+        // A path segment after `::` continues an earlier path, unless the `::`
+        // itself starts the path as in `use ::std::io`.
+        let last_code = |end: usize| masked[..end].iter().rposition(|c| !c.is_ascii_whitespace());
+        let continued = last_code(i).is_some_and(|j| {
+            if j == 0 || &masked[j - 1..=j] != b"::" {
+                return false;
+            }
+            match last_code(j - 1) {
+                Some(k) if masked[k] == b'>' => true,
+                Some(k) if is_ident_byte(masked[k]) => {
+                    let start = masked[..=k]
+                        .iter()
+                        .rposition(|&c| !is_ident_byte(c))
+                        .map_or(0, |s| s + 1);
+                    !KEYWORDS.contains(&&masked[start..=k])
+                }
+                _ => false,
+            }
+        });
+
+        let mut end = i;
+        while end < masked.len() && is_ident_byte(masked[end]) {
+            end += 1;
+        }
+        if !continued && !masked[i].is_ascii_digit() {
+            let (paths, _) = parse_tree(masked, i);
+            for path in paths {
+                if path.len() > 1 {
+                    f(i, end - i, path);
+                }
+            }
+        }
+        i = end;
+    }
+}
+
+/// Returns the text after the generic parameters at the start of `header`.
+fn skip_generics(header: &str) -> &str {
+    let header = header.trim_start();
+    if !header.starts_with('<') {
+        return header;
+    }
+
+    let mut depth = 0;
+    let mut prev = ' ';
+    for (i, c) in header.char_indices() {
+        match c {
+            '<' => depth += 1,
+            // The `>` of `->` in a closure bound does not close anything.
+            '>' if prev != '-' => {
+                depth -= 1;
+                if depth == 0 {
+                    return &header[i + 1..];
+                }
+            }
+            _ => {}
+        }
+        prev = c;
+    }
+
+    ""
+}
+
+/// Returns the last segment of the type or trait path at the start of `text`.
+fn type_name(text: &str) -> Option<String> {
+    let mut text = text.trim_start().trim_start_matches('&');
+    if text.starts_with('\'') {
+        text = text.split_once(' ').map_or("", |(_, rest)| rest);
+    }
+    let text = text.trim_start().trim_start_matches("mut ").trim_start();
+
+    let end = text
+        .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_' || c == ':'))
+        .unwrap_or(text.len());
+    let name = text[..end].rsplit("::").next()?;
+    (!name.is_empty()).then(|| name.to_owned())
+}
+
+/// Returns the self type and trait, if any, of every `impl` item in `masked`.
+fn impl_headers(masked: &[u8]) -> Vec<(String, Option<String>)> {
+    let text = String::from_utf8_lossy(masked);
+    let mut headers = Vec::new();
+
+    for (start, _) in text.match_indices("impl") {
+        let end = start + 4;
+        let line_start = masked[..start]
+            .iter()
+            .rposition(|&c| c == b'\n')
+            .map_or(0, |p| p + 1);
+        let is_item = masked[line_start..start]
+            .iter()
+            .all(|c| c.is_ascii_whitespace())
+            && masked
+                .get(end)
+                .is_some_and(|&c| c == b'<' || c.is_ascii_whitespace());
+        if !is_item {
+            continue;
+        }
+
+        let body = text[end..].find('{').map_or(text.len(), |p| end + p);
+        let header = text[end..body]
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ");
+        let header = skip_generics(&header);
+        let header = header.split(" where ").next().unwrap_or_default();
+        let (trait_name, self_type) = match header.split_once(" for ") {
+            Some((trait_path, self_type)) => (type_name(trait_path), self_type),
+            None => (None, header),
+        };
+        if let Some(self_type) = type_name(self_type) {
+            headers.push((self_type, trait_name));
+        }
+    }
+
+    headers
+}
+
+fn mod_declaration(line: &str) -> Option<(&str, &str)> {
+    let trimmed = line.trim();
+    let rest = trimmed.strip_suffix(';')?;
+    let name_start = rest.rfind(|c: char| c.is_whitespace())? + 1;
+    let (head, name) = rest.split_at(name_start);
+    let visibility = head.trim_end().strip_suffix("mod")?;
+
+    let valid_visibility = visibility.is_empty() || visibility.starts_with("pub");
+    let valid_name = name.bytes().all(is_ident_byte);
+    (valid_visibility && valid_name).then_some((visibility, name))
+}
+
+struct Module {
+    source: String,
+    children: Vec<String>,
+}
+
+/// The module tree of the library crate, keyed by module path.
+struct Crate {
+    modules: BTreeMap<Vec<String>, Module>,
+}
+
+impl Crate {
+    fn load(src_dir: &Path) -> Result<Self, String> {
+        let mut modules = BTreeMap::new();
+        Self::load_module(&src_dir.join("lib.rs"), src_dir, Vec::new(), &mut modules)?;
+
+        Ok(Self { modules })
+    }
+
+    fn load_module(
+        file: &Path,
+        dir: &Path,
+        path: Vec<String>,
+        modules: &mut BTreeMap<Vec<String>, Module>,
+    ) -> Result<(), String> {
+        let raw = fs::read_to_string(file)
+            .map_err(|err| format!("cannot read {}: {}", file.display(), err))?;
+        let source = strip(&raw);
+
+        let children: Vec<String> = source
+            .lines()
+            .filter_map(mod_declaration)
+            .map(|(_, name)| name.to_owned())
+            .collect();
+
+        for child in &children {
+            let flat = dir.join(format!("{}.rs", child));
+            let (child_file, child_dir) = if flat.exists() {
+                (flat, dir.join(child))
+            } else {
+                (dir.join(child).join("mod.rs"), dir.join(child))
+            };
+
+            let mut child_path = path.clone();
+            child_path.push(child.clone());
+            Self::load_module(&child_file, &child_dir, child_path, modules)?;
+        }
+
+        modules.insert(path, Module { source, children });
+        Ok(())
+    }
+
+    /// Resolves `rest` from module `base` to the deepest module it names.
+    fn resolve(&self, mut base: Vec<String>, rest: &[String]) -> Vec<String> {
+        for segment in rest {
+            if !self.modules[&base].children.contains(segment) {
+                break;
+            }
+            base.push(segment.clone());
+        }
+
+        base
+    }
+
+    /// Resolves `path` as written in module `current` to the module it names,
+    /// or `None` if it leads outside the crate.
+    fn resolve_from(&self, current: &[String], path: &[String]) -> Option<Vec<String>> {
+        let module = &self.modules[current];
+        let (mut base, mut rest) = (current.to_vec(), path);
+        match path[0].as_str() {
+            "crate" => {
+                base.clear();
+                rest = &path[1..];
+            }
+            "self" => rest = &path[1..],
+            "super" => {
+                while rest.first().map(String::as_str) == Some("super") {
+                    base.pop();
+                    rest = &rest[1..];
+                }
+            }
+            name if module.children.iter().any(|child| child == name) => {}
+            _ => return None,
+        }
+
+        Some(self.resolve(base, rest))
+    }
+
+    /// Returns the modules referenced from the source of module `current`.
+    fn references(&self, current: &[String]) -> Vec<Vec<String>> {
+        let mut found = Vec::new();
+        for_each_path(&mask(&self.modules[current].source), |_, _, path| {
+            found.extend(self.resolve_from(current, &path));
+        });
+
+        found
+    }
+
+    /// Returns `true` if module `current` implements methods, or a trait that
+    /// is usable, for a type imported from one of the `needed` modules.
+    fn extends(&self, current: &[String], needed: &BTreeSet<Vec<String>>) -> bool {
+        let masked = mask(&self.modules[current].source);
+        let mut imports = BTreeMap::new();
+        for_each_path(&masked, |_, _, path| {
+            if let Some(module) = self.resolve_from(current, &path) {
+                imports.insert(path.last().unwrap().clone(), module);
+            }
+        });
+        let text = String::from_utf8_lossy(&masked);
+        let defines_trait = |name: &str| text.contains(&format!("trait {}", name));
+
+        impl_headers(&masked)
+            .into_iter()
+            .any(|(self_type, trait_name)| {
+                let extends_needed = imports
+                    .get(&self_type)
+                    .is_some_and(|module| needed.contains(module));
+                // A trait of this module, or one not collected, cannot be called.
+                let usable = trait_name.is_none_or(|name| match imports.get(&name) {
+                    Some(module) => needed.contains(module),
+                    None => !defines_trait(&name),
+                });
+                extends_needed && usable
+            })
+    }
+
+    /// Adds `pending` and everything it references, transitively, to `needed`.
+    fn collect(&self, mut pending: Vec<Vec<String>>, needed: &mut BTreeSet<Vec<String>>) {
+        while let Some(path) = pending.pop() {
+            for len in 0..=path.len() {
+                if needed.insert(path[..len].to_vec()) {
+                    pending.extend(self.references(&path[..len]));
+                }
+            }
+        }
+    }
+
+    fn emit(&self, path: &mut Vec<String>, needed: &BTreeSet<Vec<String>>, out: &mut String) {
+        let source = rewrite_paths(
+            &self.modules[path.as_slice()].source,
+            "crate",
+            "crate::algorithm_rs",
+        );
+
+        for line in source.lines() {
+            match mod_declaration(line) {
+                Some((visibility, name)) => {
+                    path.push(name.to_owned());
+                    if needed.contains(path.as_slice()) {
+                        out.push_str(&format!("{}mod {} {{\n", visibility, name));
+                        self.emit(path, needed, out);
+                        out.push_str("}\n");
+                    }
+                    path.pop();
+                }
+                None => {
+                    out.push_str(line);
+                    out.push('\n');
+                }
+            }
+        }
+    }
+}
+
+/// Replaces every path starting with `from` in `src` by one starting with `to`.
+fn rewrite_paths(src: &str, from: &str, to: &str) -> String {
+    let masked = mask(src);
+    let mut edits = Vec::new();
+
+    for_each_path(&masked, |start, len, path| {
+        if path[0] != from {
+            return;
+        }
+
+        // A leading `::` marks an extern crate path, which the rewrite replaces.
+        let end = start + len;
+        let start = if start >= 2 && &masked[start - 2..start] == b"::" {
+            start - 2
+        } else {
+            start
+        };
+        // A use tree naming several items reports the same start once per item.
+        if edits.last() != Some(&(start, end)) {
+            edits.push((start, end));
+        }
+    });
+
+    let mut result = String::with_capacity(src.len());
+    let mut last = 0;
+    for (start, end) in edits {
+        result.push_str(&src[last..start]);
+        result.push_str(to);
+        last = end;
+    }
+    result.push_str(&src[last..]);
+
+    result
+}
+
+/// Bundles `solution` with the modules of the crate under `src_dir` that it uses.
+fn bundle(solution: &str, src_dir: &Path) -> Result<String, String> {
+    let library = Crate::load(src_dir)?;
+
+    let mut pending = vec![Vec::new()];
+    for_each_path(&mask(solution), |_, _, path| {
+        if path[0] == CRATE_NAME {
+            pending.push(library.resolve(Vec::new(), &path[1..]));
+        }
+    });
+
+    let mut needed = BTreeSet::new();
+    library.collect(pending, &mut needed);
+    loop {
+        let extensions: Vec<Vec<String>> = library
+            .modules
+            .keys()
+            .filter(|path| !needed.contains(*path) && library.extends(path, &needed))
+            .cloned()
+            .collect();
+        if extensions.is_empty() {
+            break;
+        }
+        library.collect(extensions, &mut needed);
+    }
+
+    let solution: String = solution
+        .lines()
+        .filter(|line| line.trim() != format!("extern crate {};", CRATE_NAME))
+        .map(|line| format!("{}\n", line))
+        .collect();
+    let mut out = rewrite_paths(&solution, CRATE_NAME, "crate::algorithm_rs");
+
+    out.push_str("\n#[allow(dead_code, unused_imports, unused_macros)]\n");
+    out.push_str(&format!("pub mod {} {{\n", CRATE_NAME));
+    library.emit(&mut Vec::new(), &needed, &mut out);
+    out.push_str("}\n");
+
+    Ok(out)
+}
+
+fn run() -> Result<(), String> {
+    let usage = "usage: bundle <main.rs> [--crate <dir>] [--output <file>]";
+    let mut args = env::args().skip(1);
+    let mut solution = None;
+    let mut crate_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let mut output = None;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--crate" => crate_dir = args.next().ok_or(usage)?.into(),
+            "--output" | "-o" => output = Some(args.next().ok_or(usage)?),
+            _ if solution.is_none() => solution = Some(arg),
+            _ => return Err(usage.to_owned()),
+        }
+    }
+
+    let solution = solution.ok_or(usage)?;
+    let source = fs::read_to_string(&solution)
+        .map_err(|err| format!("cannot read {}: {}", solution, err))?;
+    let bundled = bundle(&source, &crate_dir.join("src"))?;
+
+    match output {
+        Some(file) => {
+            fs::write(&file, bundled).map_err(|err| format!("cannot write {}: {}", file, err))
+        }
+        None => {
+            print!("{}", bundled);
+            Ok(())
+        }
+    }
+}
+
+fn main() {
+    if let Err(err) = run() {
+        eprintln!("error: {}", err);
+        process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strip() {
+        let src = r##"//! Module docs.
+
+/// Item docs.
+pub fn f() -> &'static str {
+    let _c = '"'; // trailing
+    /* block /* nested */ */
+    r#"keep // this"#
+}
+
+#[cfg(test)]
+mod tests {
+    fn g() -> char { '}' }
+}
+"##;
+
+        assert_eq!(
+            strip(src),
+            "pub fn f() -> &'static str {\n    let _c = '\"';\n    r#\"keep // this\"#\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_parse_tree() {
+        let (paths, _) = parse_tree(b"io::{Scanner, interactive::{self, Judge}};", 0);
+
+        assert_eq!(
+            paths,
+            vec![
+                vec!["io", "Scanner"],
+                vec!["io", "interactive", "self"],
+                vec!["io", "interactive", "Judge"],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_mod_declaration() {
+        assert_eq!(mod_declaration("pub mod io;"), Some(("pub ", "io")));
+        assert_eq!(mod_declaration("    mod scanner;"), Some(("", "scanner")));
+        assert_eq!(
+            mod_declaration("pub(crate) mod a;"),
+            Some(("pub(crate) ", "a"))
+        );
+        assert_eq!(mod_declaration("mod tests {"), None);
+        assert_eq!(mod_declaration("let model;"), None);
+    }
+
+    #[test]
+    fn test_rewrite_paths() {
+        assert_eq!(
+            rewrite_paths(
+                "use crate::io; pub(crate) fn f() { \"crate::x\"; }",
+                "crate",
+                "crate::a"
+            ),
+            "use crate::a::io; pub(crate) fn f() { \"crate::x\"; }"
+        );
+        assert_eq!(
+            rewrite_paths("use ::algorithm_rs::io;", CRATE_NAME, "crate::algorithm_rs"),
+            "use crate::algorithm_rs::io;"
+        );
+    }
+
+    #[test]
+    fn test_bundle() {
+        let solution = "use algorithm_rs::io::{interactive::Interactor, Writer};\n\nfn main() {}\n";
+        let bundled = bundle(solution, &Path::new(env!("CARGO_MANIFEST_DIR")).join("src")).unwrap();
+
+        assert!(bundled
+            .starts_with("use crate::algorithm_rs::io::{interactive::Interactor, Writer};\n"));
+        assert!(bundled.contains("pub mod interactive {\n"));
+        assert!(bundled.contains("mod scanner {\n"));
+        assert!(!bundled.contains("mod tests"));
+        assert!(!bundled.contains("#[cfg(test)]"));
+        assert!(!bundled.contains("///"));
+        assert!(!bundled.contains("mod math"));
+    }
+
+    #[test]
+    fn test_impl_headers() {
+        let src = b"impl<T: Fn(u32) -> u32> Foo<T> {}\n    impl Div for &fps::Series<T>\nwhere\n    T: Copy,\n{}\nfn implement() {}\nimpl<'a> Iterator for Iter<'a> {}\n";
+
+        assert_eq!(
+            impl_headers(src),
+            vec![
+                ("Foo".to_owned(), None),
+                ("Series".to_owned(), Some("Div".to_owned())),
+                ("Iter".to_owned(), Some("Iterator".to_owned())),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_bundle_follows_impls() {
+        let solution = "use algorithm_rs::math::fps::FormalPowerSeries;\n\nfn main() {}\n";
+        let bundled = bundle(solution, &Path::new(env!("CARGO_MANIFEST_DIR")).join("src")).unwrap();
+
+        // The polynomial methods on the series live in their own module.
+        assert!(bundled.contains("pub mod polynomial {\n"));
+        assert!(!bundled.contains("pub mod field {\n"));
+        assert!(!bundled.contains("pub mod io {\n"));
+    }
+}
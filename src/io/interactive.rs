@@ -0,0 +1,227 @@
+//! Line-based query/response I/O for interactive problems.
+//!
+//! Solutions talk to the judge through the [`Interactor`] trait, so the same
+//! code runs against the real judge via [`StreamInteractor`] and against an
+//! in-process [`Judge`] via [`MockInteractor`] in unit tests.
+
+use super::{FromToken, ScanError};
+use std::collections::VecDeque;
+use std::io::{self, BufRead, StdinLock, StdoutLock, Write};
+
+/// A line-oriented channel to the judge.
+pub trait Interactor {
+    /// Sends `line` to the judge and flushes it immediately.
+    fn send(&mut self, line: &str) -> Result<(), ScanError>;
+
+    /// Receives the next line from the judge without its terminator.
+    fn receive(&mut self) -> Result<String, ScanError>;
+
+    /// Receives the next line and parses it as a single token.
+    fn receive_as<T: FromToken>(&mut self) -> Result<T, ScanError> {
+        let line = self.receive()?;
+        let token = line.trim();
+
+        T::parse_token(token.as_bytes()).ok_or_else(|| ScanError::Invalid {
+            token: token.to_owned(),
+            expected: std::any::type_name::<T>(),
+        })
+    }
+
+    /// Sends `line` and returns the judge's response.
+    fn query(&mut self, line: &str) -> Result<String, ScanError> {
+        self.send(line)?;
+        self.receive()
+    }
+
+    /// Sends `line` and parses the judge's response as a single token.
+    fn query_as<T: FromToken>(&mut self, line: &str) -> Result<T, ScanError> {
+        self.send(line)?;
+        self.receive_as()
+    }
+}
+
+/// An [`Interactor`] over a reader and a writer, flushing after every line.
+pub struct StreamInteractor<R: BufRead, W: Write> {
+    reader: R,
+    writer: W,
+}
+
+impl StreamInteractor<StdinLock<'static>, StdoutLock<'static>> {
+    /// Creates an interactor over the locked standard input and output.
+    pub fn stdio() -> Self {
+        Self::new(io::stdin().lock(), io::stdout().lock())
+    }
+}
+
+impl<R: BufRead, W: Write> StreamInteractor<R, W> {
+    /// Creates an interactor that reads responses from `reader` and writes queries to `writer`.
+    pub fn new(reader: R, writer: W) -> Self {
+        Self { reader, writer }
+    }
+}
+
+impl<R: BufRead, W: Write> Interactor for StreamInteractor<R, W> {
+    fn send(&mut self, line: &str) -> Result<(), ScanError> {
+        writeln!(self.writer, "{}", line)?;
+        self.writer.flush()?;
+
+        Ok(())
+    }
+
+    fn receive(&mut self) -> Result<String, ScanError> {
+        let mut line = String::new();
+        if self.reader.read_line(&mut line)? == 0 {
+            return Err(ScanError::Eof);
+        }
+
+        let len = line.trim_end_matches(['\n', '\r']).len();
+        line.truncate(len);
+
+        Ok(line)
+    }
+}
+
+/// The judge side of an interactive problem, run in-process by [`MockInteractor`].
+pub trait Judge {
+    /// Lines sent to the solution before its first query.
+    fn start(&mut self) -> Vec<String> {
+        Vec::new()
+    }
+
+    /// Lines sent back in response to `query`.
+    fn respond(&mut self, query: &str) -> Vec<String>;
+}
+
+impl<F: FnMut(&str) -> Vec<String>> Judge for F {
+    fn respond(&mut self, query: &str) -> Vec<String> {
+        self(query)
+    }
+}
+
+/// An [`Interactor`] that forwards queries to an in-process [`Judge`].
+///
+/// # Examples
+///
+/// ```
+/// use algorithm_rs::io::interactive::{Interactor, MockInteractor};
+///
+/// let mut interactor = MockInteractor::new(|query: &str| vec![query.len().to_string()]);
+/// assert_eq!(interactor.query_as::<usize>("hello").unwrap(), 5);
+/// assert_eq!(interactor.queries(), 1);
+/// ```
+pub struct MockInteractor<J: Judge> {
+    judge: J,
+    pending: VecDeque<String>,
+    queries: usize,
+}
+
+impl<J: Judge> MockInteractor<J> {
+    /// Starts a session with `judge`, queueing its opening lines.
+    pub fn new(mut judge: J) -> Self {
+        let pending = judge.start().into();
+
+        Self {
+            judge,
+            pending,
+            queries: 0,
+        }
+    }
+
+    /// Returns the number of lines the solution has sent so far.
+    pub fn queries(&self) -> usize {
+        self.queries
+    }
+
+    /// Returns the judge, e.g. to inspect its verdict after the session.
+    pub fn judge(&self) -> &J {
+        &self.judge
+    }
+}
+
+impl<J: Judge> Interactor for MockInteractor<J> {
+    fn send(&mut self, line: &str) -> Result<(), ScanError> {
+        self.queries += 1;
+        self.pending.extend(self.judge.respond(line));
+
+        Ok(())
+    }
+
+    fn receive(&mut self) -> Result<String, ScanError> {
+        self.pending.pop_front().ok_or(ScanError::Eof)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct GuessJudge {
+        secret: u32,
+        solved: bool,
+    }
+
+    impl Judge for GuessJudge {
+        fn start(&mut self) -> Vec<String> {
+            vec!["1000".to_owned()]
+        }
+
+        fn respond(&mut self, query: &str) -> Vec<String> {
+            let guess: u32 = query.trim_start_matches("? ").parse().unwrap();
+            let verdict = match guess.cmp(&self.secret) {
+                std::cmp::Ordering::Less => "<",
+                std::cmp::Ordering::Greater => ">",
+                std::cmp::Ordering::Equal => {
+                    self.solved = true;
+                    "="
+                }
+            };
+
+            vec![verdict.to_owned()]
+        }
+    }
+
+    fn guess<I: Interactor>(interactor: &mut I) -> Result<u32, ScanError> {
+        let n: u32 = interactor.receive_as()?;
+        let (mut lo, mut hi) = (1, n);
+
+        loop {
+            let mid = (lo + hi) / 2;
+            match interactor.query(&format!("? {}", mid))?.as_str() {
+                "<" => lo = mid + 1,
+                ">" => hi = mid - 1,
+                _ => return Ok(mid),
+            }
+        }
+    }
+
+    #[test]
+    fn test_mock_judge() {
+        for secret in [1, 500, 777, 1000] {
+            let mut interactor = MockInteractor::new(GuessJudge {
+                secret,
+                solved: false,
+            });
+
+            assert_eq!(guess(&mut interactor).unwrap(), secret);
+            assert!(interactor.judge().solved);
+            assert!(interactor.queries() <= 10);
+        }
+    }
+
+    #[test]
+    fn test_mock_eof() {
+        let mut interactor = MockInteractor::new(|_: &str| Vec::new());
+
+        assert!(matches!(interactor.query("? 1"), Err(ScanError::Eof)));
+    }
+
+    #[test]
+    fn test_stream() {
+        let mut output = Vec::new();
+        let mut interactor = StreamInteractor::new(&b"1000\r\n>\n=\n"[..], &mut output);
+
+        assert_eq!(guess(&mut interactor).unwrap(), 250);
+        assert!(matches!(interactor.receive(), Err(ScanError::Eof)));
+        assert_eq!(output, b"? 500\n? 250\n");
+    }
+}
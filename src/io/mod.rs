@@ -0,0 +1,8 @@
+//! Fast input and output for contest programs.
+
+pub mod interactive;
+mod scanner;
+mod writer;
+
+pub use self::scanner::{FromToken, ScanError, Scanner};
+pub use self::writer::Writer;
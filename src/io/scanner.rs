@@ -0,0 +1,335 @@
+use std::error::Error;
+use std::fmt;
+use std::io::{self, Read};
+
+/// Error returned when the input cannot produce the requested value.
+#[derive(Debug)]
+pub enum ScanError {
+    /// Reading from the underlying source failed.
+    Io(io::Error),
+    /// The input ended before the requested token.
+    Eof,
+    /// A token was found but it does not parse as the requested type.
+    Invalid {
+        token: String,
+        expected: &'static str,
+    },
+}
+
+impl fmt::Display for ScanError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScanError::Io(err) => write!(f, "failed to read input: {}", err),
+            ScanError::Eof => write!(f, "unexpected end of input"),
+            ScanError::Invalid { token, expected } => {
+                write!(f, "cannot parse {:?} as {}", token, expected)
+            }
+        }
+    }
+}
+
+impl Error for ScanError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ScanError::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for ScanError {
+    fn from(err: io::Error) -> Self {
+        ScanError::Io(err)
+    }
+}
+
+/// Types that can be parsed from a single whitespace-separated token.
+pub trait FromToken: Sized {
+    /// Parses `token`, returning `None` if it is malformed or out of range.
+    fn parse_token(token: &[u8]) -> Option<Self>;
+}
+
+macro_rules! impl_from_token_unsigned {
+    ($($t:ty),*) => {
+        $(
+            impl FromToken for $t {
+                fn parse_token(token: &[u8]) -> Option<Self> {
+                    let digits = match token {
+                        [b'+', rest @ ..] => rest,
+                        _ => token,
+                    };
+                    if digits.is_empty() {
+                        return None;
+                    }
+
+                    let mut value: $t = 0;
+                    for &c in digits {
+                        let digit = c.wrapping_sub(b'0');
+                        if digit > 9 {
+                            return None;
+                        }
+                        value = value.checked_mul(10)?.checked_add(digit as $t)?;
+                    }
+
+                    Some(value)
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! impl_from_token_signed {
+    ($($t:ty),*) => {
+        $(
+            impl FromToken for $t {
+                fn parse_token(token: &[u8]) -> Option<Self> {
+                    let (negative, digits) = match token {
+                        [b'-', rest @ ..] => (true, rest),
+                        [b'+', rest @ ..] => (false, rest),
+                        _ => (false, token),
+                    };
+                    if digits.is_empty() {
+                        return None;
+                    }
+
+                    // Accumulate towards the sign so that `MIN` parses without overflow.
+                    let mut value: $t = 0;
+                    for &c in digits {
+                        let digit = c.wrapping_sub(b'0');
+                        if digit > 9 {
+                            return None;
+                        }
+                        value = value.checked_mul(10)?;
+                        value = if negative {
+                            value.checked_sub(digit as $t)?
+                        } else {
+                            value.checked_add(digit as $t)?
+                        };
+                    }
+
+                    Some(value)
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! impl_from_token_str {
+    ($($t:ty),*) => {
+        $(
+            impl FromToken for $t {
+                fn parse_token(token: &[u8]) -> Option<Self> {
+                    std::str::from_utf8(token).ok()?.parse().ok()
+                }
+            }
+        )*
+    };
+}
+
+impl_from_token_unsigned!(u8, u16, u32, u64, u128, usize);
+impl_from_token_signed!(i8, i16, i32, i64, i128, isize);
+impl_from_token_str!(f32, f64, char, bool);
+
+impl FromToken for String {
+    fn parse_token(token: &[u8]) -> Option<Self> {
+        String::from_utf8(token.to_vec()).ok()
+    }
+}
+
+impl FromToken for Vec<u8> {
+    fn parse_token(token: &[u8]) -> Option<Self> {
+        Some(token.to_vec())
+    }
+}
+
+/// Whitespace-separated token reader over an in-memory copy of the input.
+///
+/// The whole input is read once up front, so tokens are handed out as
+/// slices of the internal buffer without further allocation.
+///
+/// # Examples
+///
+/// ```
+/// use algorithm_rs::io::Scanner;
+///
+/// let mut scanner = Scanner::from_bytes("3\n1 -2 3\n");
+/// let n: usize = scanner.read().unwrap();
+/// let a: Vec<i64> = scanner.read_vec(n).unwrap();
+/// assert_eq!(a, vec![1, -2, 3]);
+/// ```
+pub struct Scanner {
+    buf: Vec<u8>,
+    pos: usize,
+}
+
+impl Scanner {
+    /// Reads `reader` to the end and scans its contents.
+    pub fn new<R: Read>(mut reader: R) -> Result<Self, ScanError> {
+        let mut buf = Vec::new();
+        reader.read_to_end(&mut buf)?;
+
+        Ok(Self::from_bytes(buf))
+    }
+
+    /// Reads the whole standard input and scans its contents.
+    pub fn stdin() -> Result<Self, ScanError> {
+        Self::new(io::stdin().lock())
+    }
+
+    /// Scans `bytes` directly.
+    pub fn from_bytes<B: Into<Vec<u8>>>(bytes: B) -> Self {
+        Self {
+            buf: bytes.into(),
+            pos: 0,
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.pos < self.buf.len() && self.buf[self.pos].is_ascii_whitespace() {
+            self.pos += 1;
+        }
+    }
+
+    /// Returns `true` if only whitespace remains.
+    pub fn is_exhausted(&mut self) -> bool {
+        self.skip_whitespace();
+        self.pos == self.buf.len()
+    }
+
+    /// Returns the next token as a slice of the input buffer.
+    pub fn token(&mut self) -> Result<&[u8], ScanError> {
+        self.skip_whitespace();
+        if self.pos == self.buf.len() {
+            return Err(ScanError::Eof);
+        }
+
+        let start = self.pos;
+        while self.pos < self.buf.len() && !self.buf[self.pos].is_ascii_whitespace() {
+            self.pos += 1;
+        }
+
+        Ok(&self.buf[start..self.pos])
+    }
+
+    /// Parses the next token as `T`.
+    pub fn read<T: FromToken>(&mut self) -> Result<T, ScanError> {
+        let token = self.token()?;
+
+        T::parse_token(token).ok_or_else(|| ScanError::Invalid {
+            token: String::from_utf8_lossy(token).into_owned(),
+            expected: std::any::type_name::<T>(),
+        })
+    }
+
+    /// Parses the next `n` tokens as `T`.
+    pub fn read_vec<T: FromToken>(&mut self, n: usize) -> Result<Vec<T>, ScanError> {
+        (0..n).map(|_| self.read()).collect()
+    }
+
+    /// Returns the next token as an owned byte string.
+    pub fn word(&mut self) -> Result<Vec<u8>, ScanError> {
+        self.token().map(<[u8]>::to_vec)
+    }
+
+    /// Returns the rest of the current line without its terminator.
+    ///
+    /// A trailing `\r` is stripped as well, so CRLF input behaves like LF
+    /// input. Note that after reading the last token of a line this returns
+    /// the (usually empty) remainder of that line.
+    pub fn line(&mut self) -> Result<&[u8], ScanError> {
+        if self.pos == self.buf.len() {
+            return Err(ScanError::Eof);
+        }
+
+        let start = self.pos;
+        let end = self.buf[start..]
+            .iter()
+            .position(|&c| c == b'\n')
+            .map_or(self.buf.len(), |offset| start + offset);
+        self.pos = (end + 1).min(self.buf.len());
+
+        let line = &self.buf[start..end];
+        Ok(line.strip_suffix(b"\r").unwrap_or(line))
+    }
+
+    /// Reads `rows` tokens as the rows of a character grid.
+    pub fn grid(&mut self, rows: usize) -> Result<Vec<Vec<u8>>, ScanError> {
+        (0..rows).map(|_| self.word()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_integers() {
+        let mut scanner =
+            Scanner::from_bytes("42 -7 +5 -128 255 -170141183460469231731687303715884105728");
+
+        assert_eq!(scanner.read::<u32>().unwrap(), 42);
+        assert_eq!(scanner.read::<i64>().unwrap(), -7);
+        assert_eq!(scanner.read::<usize>().unwrap(), 5);
+        assert_eq!(scanner.read::<i8>().unwrap(), i8::MIN);
+        assert_eq!(scanner.read::<u8>().unwrap(), u8::MAX);
+        assert_eq!(scanner.read::<i128>().unwrap(), i128::MIN);
+        assert!(scanner.is_exhausted());
+    }
+
+    #[test]
+    fn test_invalid_tokens() {
+        let mut scanner = Scanner::from_bytes("256 -1 12a -");
+
+        assert!(matches!(
+            scanner.read::<u8>(),
+            Err(ScanError::Invalid { ref token, expected: "u8" }) if token == "256"
+        ));
+        assert!(matches!(
+            scanner.read::<u32>(),
+            Err(ScanError::Invalid { .. })
+        ));
+        assert!(matches!(
+            scanner.read::<i32>(),
+            Err(ScanError::Invalid { .. })
+        ));
+        assert!(matches!(
+            scanner.read::<i32>(),
+            Err(ScanError::Invalid { .. })
+        ));
+        assert!(matches!(scanner.read::<i32>(), Err(ScanError::Eof)));
+    }
+
+    #[test]
+    fn test_floats_and_words() {
+        let mut scanner = Scanner::from_bytes("3.25 -1e3 hello x");
+
+        assert_eq!(scanner.read::<f64>().unwrap(), 3.25);
+        assert_eq!(scanner.read::<f32>().unwrap(), -1000.0);
+        assert_eq!(scanner.word().unwrap(), b"hello");
+        assert_eq!(scanner.read::<char>().unwrap(), 'x');
+    }
+
+    #[test]
+    fn test_lines_and_grid() {
+        let mut scanner = Scanner::from_bytes("2 3\r\n#.#\n...\nlast line\r\nno newline");
+
+        let (h, _w): (usize, usize) = (scanner.read().unwrap(), scanner.read().unwrap());
+        assert_eq!(scanner.line().unwrap(), b"");
+        assert_eq!(
+            scanner.grid(h).unwrap(),
+            vec![b"#.#".to_vec(), b"...".to_vec()]
+        );
+        assert_eq!(scanner.line().unwrap(), b"");
+        assert_eq!(scanner.line().unwrap(), b"last line");
+        assert_eq!(scanner.line().unwrap(), b"no newline");
+        assert!(matches!(scanner.line(), Err(ScanError::Eof)));
+    }
+
+    #[test]
+    fn test_reader() {
+        let mut scanner = Scanner::new(&b"1 2 3"[..]).unwrap();
+
+        assert_eq!(scanner.read_vec::<u64>(3).unwrap(), vec![1, 2, 3]);
+        assert!(matches!(scanner.token(), Err(ScanError::Eof)));
+    }
+}
@@ -0,0 +1,216 @@
+use std::fmt::{self, Display};
+use std::io::{self, BufWriter, StdoutLock, Write};
+
+/// Buffered output with helpers for the usual answer formats.
+///
+/// The buffer is flushed when the writer is dropped, so answers are never
+/// lost even if the program returns early.
+///
+/// # Examples
+///
+/// ```
+/// use algorithm_rs::io::Writer;
+///
+/// let mut out = Vec::new();
+/// {
+///     let mut writer = Writer::new(&mut out);
+///     writer.spaced(&[1, 2, 3]).unwrap();
+///     writer.yes_no(false).unwrap();
+/// }
+/// assert_eq!(out, b"1 2 3\nNo\n");
+/// ```
+pub struct Writer<W: Write = StdoutLock<'static>> {
+    inner: BufWriter<W>,
+}
+
+impl Writer {
+    /// Creates a writer over the locked standard output.
+    pub fn stdout() -> Self {
+        Self::new(io::stdout().lock())
+    }
+}
+
+impl<W: Write> Writer<W> {
+    /// Creates a writer that buffers output to `inner`.
+    pub fn new(inner: W) -> Self {
+        Self {
+            inner: BufWriter::new(inner),
+        }
+    }
+
+    /// Writes `value` without a trailing newline.
+    pub fn print<T: Display>(&mut self, value: T) -> io::Result<()> {
+        write!(self.inner, "{}", value)
+    }
+
+    /// Writes `value` followed by a newline.
+    pub fn println<T: Display>(&mut self, value: T) -> io::Result<()> {
+        writeln!(self.inner, "{}", value)
+    }
+
+    /// Writes `items` separated by `sep`, without a trailing newline.
+    pub fn join<I>(&mut self, items: I, sep: &str) -> io::Result<()>
+    where
+        I: IntoIterator,
+        I::Item: Display,
+    {
+        for (i, item) in items.into_iter().enumerate() {
+            if i > 0 {
+                self.inner.write_all(sep.as_bytes())?;
+            }
+            write!(self.inner, "{}", item)?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes `items` on one line separated by spaces.
+    pub fn spaced<I>(&mut self, items: I) -> io::Result<()>
+    where
+        I: IntoIterator,
+        I::Item: Display,
+    {
+        self.join(items, " ")?;
+        self.inner.write_all(b"\n")
+    }
+
+    /// Writes each of `items` on its own line.
+    pub fn lines<I>(&mut self, items: I) -> io::Result<()>
+    where
+        I: IntoIterator,
+        I::Item: Display,
+    {
+        for item in items {
+            writeln!(self.inner, "{}", item)?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes `Yes` or `No` on its own line.
+    pub fn yes_no(&mut self, answer: bool) -> io::Result<()> {
+        self.answer(answer, "Yes", "No")
+    }
+
+    /// Writes `yes` or `no` on its own line depending on `answer`.
+    pub fn answer(&mut self, answer: bool, yes: &str, no: &str) -> io::Result<()> {
+        self.println(if answer { yes } else { no })
+    }
+
+    /// Writes `value` with exactly `precision` digits after the decimal point.
+    ///
+    /// Values that round to zero are printed without a minus sign, as some
+    /// checkers reject `-0.00`.
+    pub fn float(&mut self, value: f64, precision: usize) -> io::Result<()> {
+        let text = format!("{:.*}", precision, value);
+        let text = match text.strip_prefix('-') {
+            Some(rest) if rest.bytes().all(|b| b == b'0' || b == b'.') => rest,
+            _ => &text,
+        };
+        self.inner.write_all(text.as_bytes())
+    }
+
+    /// Writes each row of a character grid on its own line.
+    pub fn grid<R: AsRef<[u8]>>(&mut self, rows: &[R]) -> io::Result<()> {
+        for row in rows {
+            self.inner.write_all(row.as_ref())?;
+            self.inner.write_all(b"\n")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<W: Write> Write for Writer<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.inner.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+
+    fn write_all(&mut self, buf: &[u8]) -> io::Result<()> {
+        self.inner.write_all(buf)
+    }
+
+    fn write_fmt(&mut self, args: fmt::Arguments<'_>) -> io::Result<()> {
+        self.inner.write_fmt(args)
+    }
+}
+
+impl<W: Write> Drop for Writer<W> {
+    fn drop(&mut self) {
+        // Errors cannot be reported from `drop`; call `flush` explicitly to observe them.
+        let _ = self.inner.flush();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn capture(f: impl FnOnce(&mut Writer<&mut Vec<u8>>) -> io::Result<()>) -> String {
+        let mut out = Vec::new();
+        {
+            let mut writer = Writer::new(&mut out);
+            f(&mut writer).unwrap();
+        }
+
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn test_join() {
+        assert_eq!(capture(|w| w.spaced([1, 2, 3])), "1 2 3\n");
+        assert_eq!(capture(|w| w.spaced(Vec::<i32>::new())), "\n");
+        assert_eq!(capture(|w| w.lines(vec!["a", "b"])), "a\nb\n");
+        assert_eq!(capture(|w| w.join(1..=3, ", ")), "1, 2, 3");
+    }
+
+    #[test]
+    fn test_answers() {
+        assert_eq!(
+            capture(|w| {
+                w.yes_no(true)?;
+                w.answer(false, "POSSIBLE", "IMPOSSIBLE")
+            }),
+            "Yes\nIMPOSSIBLE\n"
+        );
+    }
+
+    #[test]
+    fn test_float() {
+        assert_eq!(
+            capture(|w| {
+                w.float(1.0 / 3.0, 6)?;
+                w.print(' ')?;
+                w.float(-0.0, 2)?;
+                w.print(' ')?;
+                w.float(2.5, 0)
+            }),
+            "0.333333 0.00 2"
+        );
+        assert_eq!(
+            capture(|w| {
+                w.float(-1e-12, 2)?;
+                w.print(' ')?;
+                w.float(-0.3, 0)?;
+                w.print(' ')?;
+                w.float(-0.006, 2)
+            }),
+            "0.00 0 -0.01"
+        );
+    }
+
+    #[test]
+    fn test_grid_and_write_macro() {
+        assert_eq!(
+            capture(|w| {
+                w.grid(&[b"#.".to_vec(), b".#".to_vec()])?;
+                writeln!(w, "{}-{}", 4, 2)
+            }),
+            "#.\n.#\n4-2\n"
+        );
+    }
+}
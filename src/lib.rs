@@ -1,3 +1,11 @@
+pub mod io;
+pub mod linalg;
+pub mod math;
+pub mod num;
+
+#[cfg(test)]
+pub(crate) mod test_util;
+
 #[cfg(test)]
 mod tests {
     #[test]
@@ -0,0 +1,395 @@
+//! Gaussian elimination over a [`Field`]: rank, determinant, inverse and
+//! linear systems.
+//!
+//! Everything reduces a copy of the input to reduced row echelon form in
+//! `O(n^2 m)` field operations. Exact fields pivot on the first nonzero
+//! entry of a column and `f64` on the largest one, as chosen by
+//! [`Field::is_better_pivot`]. For `f64`, entries tiny next to the largest
+//! one of their column in the input count as zero, so rank and solvability
+//! do not change when a column is scaled.
+
+use crate::linalg::Matrix;
+use crate::math::field::Field;
+
+/// The solutions of `A x = b`: every `particular + sum c_i kernel[i]`.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Solution<T> {
+    /// One solution, with every free variable set to zero.
+    pub particular: Vec<T>,
+    /// A basis of the null space of `A`.
+    pub kernel: Vec<Vec<T>>,
+}
+
+/// The outcome of reducing the first `cols` columns of a matrix.
+struct Reduced<T> {
+    /// The column of the pivot in each of the first `rank` rows.
+    pivots: Vec<usize>,
+    /// The determinant of the reduced square block, if it has full rank.
+    det: T,
+}
+
+/// Returns the entry of column `c` that makes the best pivot, or zero if
+/// the column is zero.
+fn column_scale<T: Field>(m: &Matrix<T>, c: usize) -> T {
+    let mut scale = T::zero();
+    for i in 0..m.rows() {
+        let x = &m[(i, c)];
+        if !x.is_zero() && (scale.is_zero() || x.is_better_pivot(&scale)) {
+            scale = x.clone();
+        }
+    }
+    scale
+}
+
+/// Brings the first `cols` columns of `m` into reduced row echelon form,
+/// applying the same row operations to the remaining columns.
+fn row_reduce<T: Field>(m: &mut Matrix<T>, cols: usize) -> Reduced<T> {
+    let scales: Vec<T> = (0..cols).map(|c| column_scale(m, c)).collect();
+    let mut pivots = Vec::new();
+    let mut det = T::one();
+    for c in 0..cols {
+        let r = pivots.len();
+        if r == m.rows() {
+            break;
+        }
+
+        let mut best: Option<usize> = None;
+        for i in r..m.rows() {
+            let x = &m[(i, c)];
+            if !x.is_negligible(&scales[c]) && best.is_none_or(|b| x.is_better_pivot(&m[(b, c)])) {
+                best = Some(i);
+            }
+        }
+        let Some(p) = best else {
+            continue;
+        };
+        if p != r {
+            m.swap_rows(p, r);
+            det = -det;
+        }
+
+        let pivot = m[(r, c)].clone();
+        det = det * pivot.clone();
+        for x in &mut m.row_mut(r)[c..] {
+            *x = x.clone() / pivot.clone();
+        }
+        let pivot_row = m.row(r)[c..].to_vec();
+        for i in 0..m.rows() {
+            let f = m[(i, c)].clone();
+            if i == r || f.is_zero() {
+                continue;
+            }
+            for (x, y) in m.row_mut(i)[c..].iter_mut().zip(&pivot_row) {
+                *x = x.clone() - f.clone() * y.clone();
+            }
+        }
+        pivots.push(c);
+    }
+
+    if pivots.len() < cols {
+        det = T::zero();
+    }
+    Reduced { pivots, det }
+}
+
+/// Returns the rank of `a`.
+///
+/// # Examples
+///
+/// ```
+/// use algorithm_rs::linalg::gauss::rank;
+/// use algorithm_rs::linalg::Matrix;
+///
+/// let a = Matrix::from_rows(vec![vec![1.0, 2.0, 3.0], vec![2.0, 4.0, 6.0]]);
+/// assert_eq!(rank(&a), 1);
+/// ```
+pub fn rank<T: Field>(a: &Matrix<T>) -> usize {
+    let mut m = a.clone();
+    let cols = m.cols();
+    row_reduce(&mut m, cols).pivots.len()
+}
+
+/// Returns the determinant of the square matrix `a`.
+///
+/// # Panics
+///
+/// Panics if `a` is not square.
+///
+/// # Examples
+///
+/// ```
+/// use algorithm_rs::linalg::gauss::determinant;
+/// use algorithm_rs::linalg::Matrix;
+/// use algorithm_rs::num::Rational;
+///
+/// // The 3 x 3 Hilbert matrix.
+/// let h = Matrix::from_fn(3, 3, |i, j| Rational::new(1i64, (i + j + 1) as i64));
+/// assert_eq!(determinant(&h), Rational::new(1, 2160));
+/// ```
+pub fn determinant<T: Field>(a: &Matrix<T>) -> T {
+    assert_eq!(a.rows(), a.cols(), "matrix must be square");
+    let mut m = a.clone();
+    let cols = m.cols();
+    row_reduce(&mut m, cols).det
+}
+
+/// Returns the inverse of the square matrix `a`, or `None` if it is
+/// singular.
+///
+/// # Panics
+///
+/// Panics if `a` is not square.
+///
+/// # Examples
+///
+/// ```
+/// use algorithm_rs::linalg::gauss::inverse;
+/// use algorithm_rs::linalg::Matrix;
+/// use algorithm_rs::math::modint::ModInt998244353 as Mint;
+///
+/// let a = Matrix::from_fn(2, 2, |i, j| Mint::new(i + 2 * j + 1));
+/// assert_eq!(&a * &inverse(&a).unwrap(), Matrix::identity(2));
+/// assert_eq!(inverse(&Matrix::from_elem(2, 2, Mint::new(1))), None);
+/// ```
+pub fn inverse<T: Field>(a: &Matrix<T>) -> Option<Matrix<T>> {
+    assert_eq!(a.rows(), a.cols(), "matrix must be square");
+    let n = a.rows();
+    let mut m = Matrix::from_fn(n, 2 * n, |i, j| {
+        if j < n {
+            a[(i, j)].clone()
+        } else if j - n == i {
+            T::one()
+        } else {
+            T::zero()
+        }
+    });
+    if row_reduce(&mut m, n).pivots.len() < n {
+        return None;
+    }
+    Some(Matrix::from_fn(n, n, |i, j| m[(i, n + j)].clone()))
+}
+
+/// Solves `a x = b`, returning `None` if there is no solution.
+///
+/// # Panics
+///
+/// Panics if `b.len() != a.rows()`.
+///
+/// # Examples
+///
+/// ```
+/// use algorithm_rs::linalg::gauss::solve;
+/// use algorithm_rs::linalg::Matrix;
+/// use algorithm_rs::num::Rational;
+///
+/// let r = |x: i64| Rational::from(x);
+/// // x + y + z = 6, x - y = 1
+/// let a = Matrix::from_rows(vec![vec![r(1), r(1), r(1)], vec![r(1), r(-1), r(0)]]);
+/// let s = solve(&a, &[r(6), r(1)]).unwrap();
+/// assert_eq!(s.particular, vec![Rational::new(7, 2), Rational::new(5, 2), r(0)]);
+/// assert_eq!(s.kernel, vec![vec![Rational::new(-1, 2), Rational::new(-1, 2), r(1)]]);
+///
+/// // x + y + z = 6, 2x + 2y + 2z = 1
+/// let a = Matrix::from_rows(vec![vec![r(1); 3], vec![r(2); 3]]);
+/// assert_eq!(solve(&a, &[r(6), r(1)]), None);
+/// ```
+pub fn solve<T: Field>(a: &Matrix<T>, b: &[T]) -> Option<Solution<T>> {
+    assert_eq!(a.rows(), b.len(), "dimension mismatch");
+    let (n, m) = (a.rows(), a.cols());
+    let mut aug = Matrix::from_fn(n, m + 1, |i, j| {
+        if j < m {
+            a[(i, j)].clone()
+        } else {
+            b[i].clone()
+        }
+    });
+    let scale = column_scale(&aug, m);
+    let pivots = row_reduce(&mut aug, m).pivots;
+    if (pivots.len()..n).any(|i| !aug[(i, m)].is_negligible(&scale)) {
+        return None;
+    }
+
+    let mut particular = vec![T::zero(); m];
+    for (i, &c) in pivots.iter().enumerate() {
+        particular[c] = aug[(i, m)].clone();
+    }
+
+    // Each free column f gives x_f = 1 with the pivot variables solved for.
+    let mut is_pivot = vec![false; m];
+    for &c in &pivots {
+        is_pivot[c] = true;
+    }
+    let kernel = (0..m)
+        .filter(|&f| !is_pivot[f])
+        .map(|f| {
+            let mut v = vec![T::zero(); m];
+            v[f] = T::one();
+            for (i, &c) in pivots.iter().enumerate() {
+                v[c] = -aug[(i, f)].clone();
+            }
+            v
+        })
+        .collect();
+
+    Some(Solution { particular, kernel })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::modint::ModInt998244353;
+    use crate::num::{BigInt, Rational};
+    use crate::test_util::Rng;
+
+    type Mint = ModInt998244353;
+
+    /// Returns a random `n x m` matrix of rank at most `r`.
+    fn low_rank(rng: &mut Rng, n: usize, m: usize, r: usize) -> Matrix<Mint> {
+        let u = Matrix::from_fn(n, r, |_, _| Mint::new(rng.next()));
+        let v = Matrix::from_fn(r, m, |_, _| Mint::new(rng.next()));
+        &u * &v
+    }
+
+    #[test]
+    fn test_modint_rank_determinant_inverse() {
+        let mut rng = Rng(240);
+        for n in 1..=8 {
+            for r in 0..=n {
+                let a = low_rank(&mut rng, n, n, r);
+                assert_eq!(rank(&a), r);
+                assert_eq!(determinant(&a).is_zero(), r < n);
+                assert_eq!(inverse(&a).is_some(), r == n);
+            }
+
+            let a = Matrix::from_fn(n, n, |_, _| Mint::new(rng.next()));
+            let b = Matrix::from_fn(n, n, |_, _| Mint::new(rng.next()));
+            assert_eq!(determinant(&(&a * &b)), determinant(&a) * determinant(&b));
+            if let Some(inv) = inverse(&a) {
+                assert_eq!(&inv * &a, Matrix::identity(n));
+                assert_eq!(determinant(&inv) * determinant(&a), Mint::new(1));
+            }
+        }
+
+        // Swapping two rows flips the sign.
+        let mut a = Matrix::from_fn(5, 5, |_, _| Mint::new(rng.next()));
+        let det = determinant(&a);
+        a.swap_rows(1, 3);
+        assert_eq!(determinant(&a), -det);
+    }
+
+    #[test]
+    fn test_modint_solve() {
+        let mut rng = Rng(241);
+        for _ in 0..200 {
+            let n = (rng.next() % 6 + 1) as usize;
+            let m = (rng.next() % 6 + 1) as usize;
+            let r = (rng.next() % (n.min(m) as u64 + 1)) as usize;
+            let a = low_rank(&mut rng, n, m, r);
+
+            let x: Vec<Mint> = (0..m).map(|_| Mint::new(rng.next())).collect();
+            let b = a.mul_vec(&x);
+            let s = solve(&a, &b).unwrap();
+            assert_eq!(a.mul_vec(&s.particular), b);
+            assert_eq!(s.kernel.len(), m - r);
+            for v in &s.kernel {
+                assert_eq!(a.mul_vec(v), vec![Mint::new(0); n]);
+            }
+            let basis = Matrix::from_rows(s.kernel.clone());
+            assert_eq!(rank(&basis), m - r);
+
+            // A right-hand side outside the column space.
+            if r < n {
+                let b: Vec<Mint> = (0..n).map(|_| Mint::new(rng.next())).collect();
+                let full = Matrix::from_fn(n, m + 1, |i, j| if j < m { a[(i, j)] } else { b[i] });
+                assert_eq!(solve(&a, &b).is_some(), rank(&full) == r);
+            }
+        }
+    }
+
+    #[test]
+    fn test_rational_hilbert() {
+        // det H_n = 1 / prod_{k < n} (2k + 1) C(2k, k)^2
+        let mut expected = BigInt::one();
+        let mut binom = BigInt::one();
+        for n in 1..=12i64 {
+            let k = n - 1;
+            if k > 0 {
+                binom = binom * BigInt::from(2 * (2 * k - 1)) / BigInt::from(k);
+            }
+            expected = expected * BigInt::from(2 * k + 1) * binom.clone() * binom.clone();
+
+            let h = Matrix::from_fn(n as usize, n as usize, |i, j| {
+                Rational::new(BigInt::one(), BigInt::from(i + j + 1))
+            });
+            assert_eq!(
+                determinant(&h),
+                Rational::new(BigInt::one(), expected.clone())
+            );
+            let inv = inverse(&h).unwrap();
+            assert_eq!(&inv * &h, Matrix::identity(n as usize));
+            // The inverse of a Hilbert matrix has integer entries.
+            assert!((0..n as usize).all(|i| inv.row(i).iter().all(|x| x.is_integer())));
+        }
+    }
+
+    #[test]
+    fn test_f64_partial_pivoting() {
+        // Without row swaps the tiny pivot wipes out the answer.
+        let a = Matrix::from_rows(vec![vec![1e-20, 1.0], vec![1.0, 1.0]]);
+        let x = solve(&a, &[1.0, 2.0]).unwrap().particular;
+        assert!((x[0] - 1.0).abs() < 1e-12 && (x[1] - 1.0).abs() < 1e-12);
+
+        let mut rng = Rng(242);
+        for n in 1..=10 {
+            let a = Matrix::from_fn(n, n, |_, _| (rng.next() % 2001) as f64 / 100.0 - 10.0);
+            let x: Vec<f64> = (0..n).map(|_| (rng.next() % 201) as f64 - 100.0).collect();
+            let s = solve(&a, &a.mul_vec(&x)).unwrap();
+            assert!(s.kernel.is_empty());
+            for (got, want) in s.particular.iter().zip(&x) {
+                assert!((got - want).abs() < 1e-6, "{} vs {}", got, want);
+            }
+            let inv = inverse(&a).unwrap();
+            let id = &inv * &a;
+            for i in 0..n {
+                for j in 0..n {
+                    let want = if i == j { 1.0 } else { 0.0 };
+                    assert!((id[(i, j)] - want).abs() < 1e-9);
+                }
+            }
+        }
+        assert_eq!(
+            rank(&Matrix::from_rows(vec![vec![1.0, 2.0], vec![0.5, 1.0]])),
+            1
+        );
+    }
+
+    #[test]
+    fn test_f64_tolerance_follows_scale() {
+        let mut rng = Rng(243);
+        for scale in [1e-15, 1e-6, 1.0, 1e6, 1e15] {
+            for n in 1..=8 {
+                // Thirds do not round exactly, so products leave noise.
+                let entry = |rng: &mut Rng| ((rng.next() % 61) as f64 - 30.0) / 3.0;
+                let r = (rng.next() % n as u64 + 1) as usize;
+                let u = Matrix::from_fn(n, r, |_, _| entry(&mut rng) * scale);
+                let v = Matrix::from_fn(r, n + 1, |_, _| entry(&mut rng));
+                let full = &u * &v;
+                assert_eq!(rank(&full), rank(&v), "scale = {}, n = {}", scale, n);
+
+                let a = Matrix::from_fn(n, n, |i, j| full[(i, j)]);
+                let b: Vec<f64> = (0..n).map(|i| full[(i, n)]).collect();
+                let s = solve(&a, &b).expect("b lies in the column space");
+                for (got, want) in a.mul_vec(&s.particular).iter().zip(&b) {
+                    assert!((got - want).abs() <= 1e-6 * scale, "{} vs {}", got, want);
+                }
+
+                if rank(&a) < n {
+                    let mut b = b;
+                    b[0] += scale;
+                    let e = Matrix::from_fn(n, n + 1, |i, j| if j < n { a[(i, j)] } else { b[i] });
+                    assert_eq!(solve(&a, &b).is_some(), rank(&e) == rank(&a));
+                }
+            }
+        }
+    }
+}
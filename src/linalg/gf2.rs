@@ -0,0 +1,338 @@
+//! Bit-packed linear algebra over GF(2).
+//!
+//! Rows are stored as 64-bit words, so eliminating an `n x m` system costs
+//! `O(n min(n, m) m / 64)` word operations and thousands of variables are
+//! routine. The API mirrors [`gauss`](crate::linalg::gauss), with `bool`
+//! for field elements.
+
+use crate::linalg::gauss::Solution;
+
+/// A dense `rows x cols` matrix over GF(2) with each row packed into words.
+///
+/// # Examples
+///
+/// ```
+/// use algorithm_rs::linalg::gf2::{self, BitMatrix};
+///
+/// // x0 ^ x1 = 1, x1 ^ x2 = 0
+/// let a = BitMatrix::from_fn(2, 3, |i, j| j == i || j == i + 1);
+/// let s = gf2::solve(&a, &[true, false]).unwrap();
+/// assert_eq!(s.particular, vec![true, false, false]);
+/// assert_eq!(s.kernel, vec![vec![true, true, true]]);
+/// assert_eq!(gf2::rank(&a), 2);
+/// ```
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct BitMatrix {
+    rows: usize,
+    cols: usize,
+    words: usize,
+    data: Vec<u64>,
+}
+
+impl BitMatrix {
+    /// Returns the `rows x cols` zero matrix.
+    pub fn new(rows: usize, cols: usize) -> Self {
+        let words = cols.div_ceil(64);
+        Self {
+            rows,
+            cols,
+            words,
+            data: vec![0; rows * words],
+        }
+    }
+
+    /// Returns the matrix whose entry `(i, j)` is `f(i, j)`.
+    pub fn from_fn<F: FnMut(usize, usize) -> bool>(rows: usize, cols: usize, mut f: F) -> Self {
+        let mut m = Self::new(rows, cols);
+        for i in 0..rows {
+            for j in 0..cols {
+                if f(i, j) {
+                    m.set(i, j, true);
+                }
+            }
+        }
+        m
+    }
+
+    /// Returns the `n x n` identity matrix.
+    pub fn identity(n: usize) -> Self {
+        Self::from_fn(n, n, |i, j| i == j)
+    }
+
+    /// Returns the number of rows.
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    /// Returns the number of columns.
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    /// Returns entry `(i, j)`.
+    pub fn get(&self, i: usize, j: usize) -> bool {
+        assert!(j < self.cols, "column index out of bounds");
+        self.row(i)[j / 64] >> (j % 64) & 1 == 1
+    }
+
+    /// Sets entry `(i, j)` to `value`.
+    pub fn set(&mut self, i: usize, j: usize, value: bool) {
+        assert!(j < self.cols, "column index out of bounds");
+        let word = &mut self.row_mut(i)[j / 64];
+        *word = *word & !(1 << (j % 64)) | (value as u64) << (j % 64);
+    }
+
+    /// Returns row `i` as words; bit `j % 64` of word `j / 64` is column `j`
+    /// and the bits past the last column are zero.
+    pub fn row(&self, i: usize) -> &[u64] {
+        &self.data[i * self.words..(i + 1) * self.words]
+    }
+
+    fn row_mut(&mut self, i: usize) -> &mut [u64] {
+        &mut self.data[i * self.words..(i + 1) * self.words]
+    }
+
+    /// Swaps rows `i` and `j`.
+    pub fn swap_rows(&mut self, i: usize, j: usize) {
+        if i != j {
+            let (lo, hi) = (i.min(j), i.max(j));
+            let (head, tail) = self.data.split_at_mut(hi * self.words);
+            head[lo * self.words..(lo + 1) * self.words].swap_with_slice(&mut tail[..self.words]);
+        }
+    }
+
+    /// Adds row `src` to row `dst`, from word `from` on.
+    fn xor_row(&mut self, dst: usize, src: usize, from: usize) {
+        let w = self.words;
+        let (d, s) = if dst < src {
+            let (head, tail) = self.data.split_at_mut(src * w);
+            (&mut head[dst * w..(dst + 1) * w], &tail[..w])
+        } else {
+            let (head, tail) = self.data.split_at_mut(dst * w);
+            (&mut tail[..w], &head[src * w..(src + 1) * w])
+        };
+        for (x, y) in d[from..].iter_mut().zip(&s[from..]) {
+            *x ^= y;
+        }
+    }
+
+    /// Returns `self * x` for a column vector `x`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `x.len() != self.cols()`.
+    pub fn mul_vec(&self, x: &[bool]) -> Vec<bool> {
+        assert_eq!(x.len(), self.cols, "dimension mismatch");
+        let packed = pack(x);
+        (0..self.rows)
+            .map(|i| {
+                let ones: u32 = self
+                    .row(i)
+                    .iter()
+                    .zip(&packed)
+                    .map(|(a, b)| (a & b).count_ones())
+                    .sum();
+                ones % 2 == 1
+            })
+            .collect()
+    }
+}
+
+/// Packs `bits` into words, low bits first.
+fn pack(bits: &[bool]) -> Vec<u64> {
+    let mut words = vec![0; bits.len().div_ceil(64)];
+    for (j, &b) in bits.iter().enumerate() {
+        words[j / 64] |= (b as u64) << (j % 64);
+    }
+    words
+}
+
+/// Brings the first `cols` columns of `m` into reduced row echelon form and
+/// returns the pivot column of each of the first `rank` rows.
+fn row_reduce(m: &mut BitMatrix, cols: usize) -> Vec<usize> {
+    let mut pivots = Vec::new();
+    for c in 0..cols {
+        let r = pivots.len();
+        if r == m.rows {
+            break;
+        }
+        let Some(p) = (r..m.rows).find(|&i| m.get(i, c)) else {
+            continue;
+        };
+        m.swap_rows(p, r);
+        for i in 0..m.rows {
+            if i != r && m.get(i, c) {
+                m.xor_row(i, r, c / 64);
+            }
+        }
+        pivots.push(c);
+    }
+    pivots
+}
+
+/// Returns the rank of `a` over GF(2).
+pub fn rank(a: &BitMatrix) -> usize {
+    let mut m = a.clone();
+    let cols = m.cols;
+    row_reduce(&mut m, cols).len()
+}
+
+/// Returns the inverse of the square matrix `a`, or `None` if it is
+/// singular.
+///
+/// # Panics
+///
+/// Panics if `a` is not square.
+pub fn inverse(a: &BitMatrix) -> Option<BitMatrix> {
+    assert_eq!(a.rows, a.cols, "matrix must be square");
+    let n = a.rows;
+    let mut m = BitMatrix::from_fn(
+        n,
+        2 * n,
+        |i, j| if j < n { a.get(i, j) } else { j - n == i },
+    );
+    if row_reduce(&mut m, n).len() < n {
+        return None;
+    }
+    Some(BitMatrix::from_fn(n, n, |i, j| m.get(i, n + j)))
+}
+
+/// Solves `a x = b` over GF(2), returning `None` if there is no solution.
+///
+/// # Panics
+///
+/// Panics if `b.len() != a.rows()`.
+pub fn solve(a: &BitMatrix, b: &[bool]) -> Option<Solution<bool>> {
+    assert_eq!(a.rows, b.len(), "dimension mismatch");
+    let (n, m) = (a.rows, a.cols);
+    // Copy whole words, then place b in the extra column.
+    let mut aug = BitMatrix::new(n, m + 1);
+    for (i, &bi) in b.iter().enumerate() {
+        aug.row_mut(i)[..a.words].copy_from_slice(a.row(i));
+        aug.set(i, m, bi);
+    }
+    let pivots = row_reduce(&mut aug, m);
+    if (pivots.len()..n).any(|i| aug.get(i, m)) {
+        return None;
+    }
+
+    let mut particular = vec![false; m];
+    for (i, &c) in pivots.iter().enumerate() {
+        particular[c] = aug.get(i, m);
+    }
+
+    let mut is_pivot = vec![false; m];
+    for &c in &pivots {
+        is_pivot[c] = true;
+    }
+    let kernel = (0..m)
+        .filter(|&f| !is_pivot[f])
+        .map(|f| {
+            let mut v = vec![false; m];
+            v[f] = true;
+            for (i, &c) in pivots.iter().enumerate() {
+                v[c] = aug.get(i, f);
+            }
+            v
+        })
+        .collect();
+
+    Some(Solution { particular, kernel })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linalg::xor_basis::XorBasis;
+    use crate::test_util::Rng;
+
+    /// Returns a random `n x m` matrix of rank at most `r` as a product.
+    fn low_rank(rng: &mut Rng, n: usize, m: usize, r: usize) -> BitMatrix {
+        let u = BitMatrix::from_fn(n, r, |_, _| rng.bit());
+        let v = BitMatrix::from_fn(r, m, |_, _| rng.bit());
+        let vt: Vec<Vec<bool>> = (0..m)
+            .map(|j| (0..r).map(|k| v.get(k, j)).collect())
+            .collect();
+        let mut a = BitMatrix::new(n, m);
+        for (j, col) in vt.iter().enumerate() {
+            for (i, x) in u.mul_vec(col).into_iter().enumerate() {
+                a.set(i, j, x);
+            }
+        }
+        a
+    }
+
+    #[test]
+    fn test_rank_against_xor_basis() {
+        let mut rng = Rng(252);
+        for _ in 0..300 {
+            let n = (rng.next() % 20) as usize;
+            let m = (rng.next() % 64 + 1) as usize;
+            let a = BitMatrix::from_fn(n, m, |_, _| rng.bit() && rng.bit());
+            let basis: XorBasis<u64> = (0..n).map(|i| a.row(i)[0]).collect();
+            assert_eq!(rank(&a), basis.len());
+        }
+    }
+
+    #[test]
+    fn test_solve_and_inverse() {
+        let mut rng = Rng(253);
+        for _ in 0..100 {
+            let n = (rng.next() % 150 + 1) as usize;
+            let m = (rng.next() % 150 + 1) as usize;
+            let r = (rng.next() % (n.min(m) as u64 + 1)) as usize;
+            let a = low_rank(&mut rng, n, m, r);
+            let rk = rank(&a);
+            assert!(rk <= r);
+
+            let x: Vec<bool> = (0..m).map(|_| rng.bit()).collect();
+            let b = a.mul_vec(&x);
+            let s = solve(&a, &b).unwrap();
+            assert_eq!(a.mul_vec(&s.particular), b);
+            assert_eq!(s.kernel.len(), m - rk);
+            for v in &s.kernel {
+                assert!(a.mul_vec(v).iter().all(|&y| !y));
+            }
+
+            if rk < n {
+                let b: Vec<bool> = (0..n).map(|_| rng.bit()).collect();
+                let full =
+                    BitMatrix::from_fn(n, m + 1, |i, j| if j < m { a.get(i, j) } else { b[i] });
+                assert_eq!(solve(&a, &b).is_some(), rank(&full) == rk);
+            }
+        }
+
+        for n in [1, 63, 64, 65, 200] {
+            let a = BitMatrix::from_fn(n, n, |_, _| rng.bit());
+            match inverse(&a) {
+                Some(inv) => {
+                    assert_eq!(rank(&a), n);
+                    for j in 0..n {
+                        let col: Vec<bool> = (0..n).map(|i| inv.get(i, j)).collect();
+                        let e: Vec<bool> = (0..n).map(|i| i == j).collect();
+                        assert_eq!(a.mul_vec(&col), e);
+                    }
+                }
+                None => assert!(rank(&a) < n),
+            }
+        }
+        assert_eq!(
+            inverse(&BitMatrix::identity(100)),
+            Some(BitMatrix::identity(100))
+        );
+    }
+
+    #[test]
+    fn test_thousands_of_variables() {
+        let mut rng = Rng(254);
+        let (n, m) = (2_000, 2_500);
+        let a = BitMatrix::from_fn(n, m, |_, _| rng.bit());
+        let x: Vec<bool> = (0..m).map(|_| rng.bit()).collect();
+        let b = a.mul_vec(&x);
+        let s = solve(&a, &b).unwrap();
+        assert_eq!(a.mul_vec(&s.particular), b);
+        // A random wide matrix has full row rank with overwhelming odds.
+        assert_eq!(s.kernel.len(), m - n);
+        assert!(a.mul_vec(&s.kernel[0]).iter().all(|&y| !y));
+    }
+}
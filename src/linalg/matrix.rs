@@ -0,0 +1,329 @@
+//! Dense row-major matrices.
+
+use crate::linalg::semiring::Semiring;
+use std::ops::{Index, IndexMut, Mul};
+
+/// A dense `rows x cols` matrix stored row by row.
+///
+/// Storage and indexing work for any `T`; multiplication and powers need a
+/// [`Semiring`], so one implementation counts walks over a modint, finds
+/// shortest walks over [`MinPlus`](crate::linalg::semiring::MinPlus) and
+/// tests reachability over [`Boolean`](crate::linalg::semiring::Boolean).
+///
+/// # Examples
+///
+/// ```
+/// use algorithm_rs::linalg::semiring::MinPlus;
+/// use algorithm_rs::linalg::Matrix;
+/// use algorithm_rs::math::modint::ModInt998244353 as Mint;
+///
+/// // Fibonacci numbers from powers of [[1, 1], [1, 0]].
+/// let fib = Matrix::from_rows(vec![
+///     vec![Mint::new(1), Mint::new(1)],
+///     vec![Mint::new(1), Mint::new(0)],
+/// ]);
+/// assert_eq!(fib.pow(90)[(0, 1)], Mint::new(2_880_067_194_370_816_120u64));
+///
+/// // The shortest walk with exactly 3 edges from 0 to 1.
+/// let inf = MinPlus::INF;
+/// let g = Matrix::from_rows(vec![
+///     vec![inf, MinPlus(5), MinPlus(1)],
+///     vec![MinPlus(1), inf, inf],
+///     vec![MinPlus(1), MinPlus(1), inf],
+/// ]);
+/// assert_eq!(g.pow(3)[(0, 1)], MinPlus(7));
+/// ```
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Matrix<T> {
+    rows: usize,
+    cols: usize,
+    data: Vec<T>,
+}
+
+impl<T> Matrix<T> {
+    /// Returns the matrix whose entry `(i, j)` is `f(i, j)`.
+    pub fn from_fn<F: FnMut(usize, usize) -> T>(rows: usize, cols: usize, mut f: F) -> Self {
+        let data = (0..rows * cols).map(|k| f(k / cols, k % cols)).collect();
+        Self { rows, cols, data }
+    }
+
+    /// Builds a matrix from its rows.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the rows differ in length.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Self {
+        let cols = rows.first().map_or(0, Vec::len);
+        let n = rows.len();
+        let mut data = Vec::with_capacity(n * cols);
+        for row in rows {
+            assert_eq!(row.len(), cols, "rows must have equal length");
+            data.extend(row);
+        }
+        Self {
+            rows: n,
+            cols,
+            data,
+        }
+    }
+
+    /// Returns the number of rows.
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    /// Returns the number of columns.
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    /// Returns row `i`.
+    pub fn row(&self, i: usize) -> &[T] {
+        &self.data[i * self.cols..(i + 1) * self.cols]
+    }
+
+    /// Returns row `i` mutably.
+    pub fn row_mut(&mut self, i: usize) -> &mut [T] {
+        &mut self.data[i * self.cols..(i + 1) * self.cols]
+    }
+
+    /// Swaps rows `i` and `j`.
+    pub fn swap_rows(&mut self, i: usize, j: usize) {
+        if i != j {
+            let (lo, hi) = (i.min(j), i.max(j));
+            let (head, tail) = self.data.split_at_mut(hi * self.cols);
+            head[lo * self.cols..(lo + 1) * self.cols].swap_with_slice(&mut tail[..self.cols]);
+        }
+    }
+
+    /// Returns the rows as vectors.
+    pub fn into_rows(self) -> Vec<Vec<T>> {
+        let mut data = self.data.into_iter();
+        (0..self.rows)
+            .map(|_| data.by_ref().take(self.cols).collect())
+            .collect()
+    }
+}
+
+impl<T: Clone> Matrix<T> {
+    /// Returns the `rows x cols` matrix filled with `value`.
+    pub fn from_elem(rows: usize, cols: usize, value: T) -> Self {
+        Self {
+            rows,
+            cols,
+            data: vec![value; rows * cols],
+        }
+    }
+
+    /// Returns the transpose.
+    pub fn transpose(&self) -> Self {
+        Self::from_fn(self.cols, self.rows, |i, j| self[(j, i)].clone())
+    }
+}
+
+impl<T: Semiring> Matrix<T> {
+    /// Returns the `rows x cols` matrix of zeros.
+    pub fn zero(rows: usize, cols: usize) -> Self {
+        Self::from_elem(rows, cols, T::zero())
+    }
+
+    /// Returns the `n x n` identity matrix.
+    pub fn identity(n: usize) -> Self {
+        Self::from_fn(n, n, |i, j| if i == j { T::one() } else { T::zero() })
+    }
+
+    /// Returns `self * v` for a column vector `v`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `v.len() != self.cols()`.
+    pub fn mul_vec(&self, v: &[T]) -> Vec<T> {
+        assert_eq!(v.len(), self.cols, "dimension mismatch");
+        (0..self.rows)
+            .map(|i| {
+                self.row(i)
+                    .iter()
+                    .zip(v)
+                    .fold(T::zero(), |acc, (a, b)| acc + a.clone() * b.clone())
+            })
+            .collect()
+    }
+
+    /// Returns `self` raised to the `exp`-th power in `O(n^3 log exp)`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the matrix is not square.
+    pub fn pow(&self, mut exp: u64) -> Self {
+        assert_eq!(self.rows, self.cols, "matrix must be square");
+        let mut result = Self::identity(self.rows);
+        let mut base = self.clone();
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result = &result * &base;
+            }
+            exp >>= 1;
+            if exp > 0 {
+                base = &base * &base;
+            }
+        }
+
+        result
+    }
+}
+
+impl<T> Index<(usize, usize)> for Matrix<T> {
+    type Output = T;
+
+    fn index(&self, (i, j): (usize, usize)) -> &T {
+        assert!(j < self.cols, "column index out of bounds");
+        &self.data[i * self.cols + j]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Matrix<T> {
+    fn index_mut(&mut self, (i, j): (usize, usize)) -> &mut T {
+        assert!(j < self.cols, "column index out of bounds");
+        &mut self.data[i * self.cols + j]
+    }
+}
+
+impl<T: Semiring> Mul for &Matrix<T> {
+    type Output = Matrix<T>;
+
+    /// # Panics
+    ///
+    /// Panics if `self.cols() != rhs.rows()`.
+    fn mul(self, rhs: Self) -> Matrix<T> {
+        assert_eq!(self.cols, rhs.rows, "dimension mismatch");
+        let mut result = Matrix::<T>::zero(self.rows, rhs.cols);
+        // i-k-j order walks both `rhs` and `result` along rows.
+        for i in 0..self.rows {
+            let out = result.row_mut(i);
+            for (k, a) in self.row(i).iter().enumerate() {
+                for (c, b) in out.iter_mut().zip(rhs.row(k)) {
+                    *c = c.clone() + a.clone() * b.clone();
+                }
+            }
+        }
+
+        result
+    }
+}
+
+impl<T: Semiring> Mul for Matrix<T> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        &self * &rhs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linalg::semiring::{Boolean, MaxPlus, MinPlus};
+    use crate::math::modint::ModInt998244353;
+    use crate::test_util::Rng;
+
+    type Mint = ModInt998244353;
+
+    /// Returns a random weighted digraph on `n` vertices as an edge list.
+    fn random_graph(rng: &mut Rng, n: usize) -> Vec<(usize, usize, i64)> {
+        let mut edges = Vec::new();
+        for u in 0..n {
+            for v in 0..n {
+                if rng.next().is_multiple_of(3) {
+                    edges.push((u, v, (rng.next() % 21) as i64 - 5));
+                }
+            }
+        }
+        edges
+    }
+
+    #[test]
+    fn test_walks_of_length_k() {
+        let mut rng = Rng(230);
+        for n in 1..=6 {
+            let edges = random_graph(&mut rng, n);
+            let count = Matrix::from_fn(n, n, |u, v| {
+                Mint::new(edges.iter().filter(|e| (e.0, e.1) == (u, v)).count())
+            });
+            let shortest = Matrix::from_fn(n, n, |u, v| {
+                edges
+                    .iter()
+                    .filter(|e| (e.0, e.1) == (u, v))
+                    .map(|e| MinPlus(e.2))
+                    .fold(MinPlus::INF, |a, b| a + b)
+            });
+            let longest = Matrix::from_fn(n, n, |u, v| {
+                edges
+                    .iter()
+                    .filter(|e| (e.0, e.1) == (u, v))
+                    .map(|e| MaxPlus(e.2))
+                    .fold(MaxPlus::NEG_INF, |a, b| a + b)
+            });
+            let reach = Matrix::from_fn(n, n, |u, v| {
+                Boolean(edges.iter().any(|e| (e.0, e.1) == (u, v)))
+            });
+
+            // dp[k][v]: walks of exactly k edges from vertex 0.
+            let mut ways = vec![Mint::new(0); n];
+            let mut best = vec![None::<(i64, i64)>; n];
+            ways[0] = Mint::new(1);
+            best[0] = Some((0, 0));
+            for k in 0..=12u64 {
+                assert_eq!(count.pow(k).row(0), &ways[..], "n = {}, k = {}", n, k);
+                let (lo, hi) = (shortest.pow(k), longest.pow(k));
+                let reachable = reach.pow(k);
+                for (v, b) in best.iter().enumerate() {
+                    let (l, h) = b.unwrap_or((i64::MAX, i64::MIN));
+                    assert_eq!(lo[(0, v)], MinPlus(l));
+                    assert_eq!(hi[(0, v)], MaxPlus(h));
+                    assert_eq!(reachable[(0, v)], Boolean(b.is_some()));
+                }
+
+                let mut next_ways = vec![Mint::new(0); n];
+                let mut next_best = vec![None::<(i64, i64)>; n];
+                for &(u, v, w) in &edges {
+                    next_ways[v] += ways[u];
+                    if let Some((lo, hi)) = best[u] {
+                        let (a, b) = next_best[v].unwrap_or((i64::MAX, i64::MIN));
+                        next_best[v] = Some((a.min(lo + w), b.max(hi + w)));
+                    }
+                }
+                ways = next_ways;
+                best = next_best;
+            }
+        }
+    }
+
+    #[test]
+    fn test_storage_and_products() {
+        let mut rng = Rng(231);
+        let random =
+            |rng: &mut Rng, r: usize, c: usize| Matrix::from_fn(r, c, |_, _| Mint::new(rng.next()));
+        let (a, b, c) = (
+            random(&mut rng, 3, 5),
+            random(&mut rng, 5, 4),
+            random(&mut rng, 4, 2),
+        );
+        assert_eq!(&(&a * &b) * &c, &a * &(&b * &c));
+        assert_eq!((&a * &b).transpose(), &b.transpose() * &a.transpose());
+        assert_eq!(&Matrix::identity(3) * &a, a);
+        assert_eq!(&a * &Matrix::zero(5, 1), Matrix::zero(3, 1));
+
+        let v: Vec<Mint> = (0..5).map(|_| Mint::new(rng.next())).collect();
+        let column = Matrix::from_fn(5, 1, |i, _| v[i]);
+        assert_eq!(a.mul_vec(&v), (&a * &column).into_rows().concat());
+
+        let m = random(&mut rng, 4, 4);
+        assert_eq!(m.pow(0), Matrix::identity(4));
+        assert_eq!(m.pow(13), &m.pow(6) * &m.pow(7));
+
+        let mut s = Matrix::from_rows(vec![vec![1, 2], vec![3, 4], vec![5, 6]]);
+        s.swap_rows(2, 0);
+        s[(1, 0)] = 7;
+        assert_eq!(s.into_rows(), vec![vec![5, 6], vec![7, 4], vec![1, 2]]);
+    }
+}
@@ -0,0 +1,10 @@
+//! Matrices over semirings and linear algebra over fields.
+
+pub mod gauss;
+pub mod gf2;
+mod matrix;
+pub mod semiring;
+pub mod xor_basis;
+
+pub use self::matrix::Matrix;
+pub use self::semiring::Semiring;
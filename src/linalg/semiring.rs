@@ -0,0 +1,175 @@
+//! The semiring abstraction behind [`Matrix`](crate::linalg::Matrix)
+//! multiplication.
+//!
+//! Replacing `(+, *)` by another pair of operations turns the same matrix
+//! power into a different walk problem: `(min, +)` gives shortest walks of
+//! exactly `k` edges, `(max, +)` the longest ones, and `(or, and)` which
+//! vertices are reachable in exactly `k` steps.
+
+use crate::math::field::Field;
+use std::fmt::Debug;
+use std::ops::{Add, Mul};
+
+/// A set with an associative, commutative `+` and an associative `*` that
+/// distributes over it, with identities [`zero`](Semiring::zero) and
+/// [`one`](Semiring::one), where `zero` annihilates under `*`.
+///
+/// Every [`Field`] implements it with its usual arithmetic, which covers the
+/// modints, [`Rational`](crate::num::Rational) and `f64`.
+pub trait Semiring: Clone + PartialEq + Debug + Add<Output = Self> + Mul<Output = Self> {
+    /// Returns the identity of `+`.
+    fn zero() -> Self;
+
+    /// Returns the identity of `*`.
+    fn one() -> Self;
+}
+
+impl<T: Field> Semiring for T {
+    fn zero() -> Self {
+        Field::zero()
+    }
+
+    fn one() -> Self {
+        Field::one()
+    }
+}
+
+/// The tropical semiring `(min, +)` on `i64`, with `i64::MAX` as infinity.
+///
+/// Sums saturate, so a finite path whose length overflows becomes infinite
+/// and weights should stay well inside the range.
+///
+/// # Examples
+///
+/// ```
+/// use algorithm_rs::linalg::semiring::MinPlus;
+///
+/// assert_eq!(MinPlus(3) + MinPlus(5), MinPlus(3));
+/// assert_eq!(MinPlus(3) * MinPlus(5), MinPlus(8));
+/// assert_eq!(MinPlus(3) * MinPlus::INF, MinPlus::INF);
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct MinPlus(pub i64);
+
+impl MinPlus {
+    /// The additive identity, standing for "no path".
+    pub const INF: Self = Self(i64::MAX);
+}
+
+impl Add for MinPlus {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self(self.0.min(rhs.0))
+    }
+}
+
+impl Mul for MinPlus {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        if self == Self::INF || rhs == Self::INF {
+            Self::INF
+        } else {
+            Self(self.0.saturating_add(rhs.0))
+        }
+    }
+}
+
+impl Semiring for MinPlus {
+    fn zero() -> Self {
+        Self::INF
+    }
+
+    fn one() -> Self {
+        Self(0)
+    }
+}
+
+/// The semiring `(max, +)` on `i64`, with `i64::MIN` as negative infinity.
+///
+/// # Examples
+///
+/// ```
+/// use algorithm_rs::linalg::semiring::MaxPlus;
+///
+/// assert_eq!(MaxPlus(3) + MaxPlus(5), MaxPlus(5));
+/// assert_eq!(MaxPlus(3) * MaxPlus(5), MaxPlus(8));
+/// assert_eq!(MaxPlus(3) * MaxPlus::NEG_INF, MaxPlus::NEG_INF);
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct MaxPlus(pub i64);
+
+impl MaxPlus {
+    /// The additive identity, standing for "no path".
+    pub const NEG_INF: Self = Self(i64::MIN);
+}
+
+impl Add for MaxPlus {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self(self.0.max(rhs.0))
+    }
+}
+
+impl Mul for MaxPlus {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        if self == Self::NEG_INF || rhs == Self::NEG_INF {
+            Self::NEG_INF
+        } else {
+            Self(self.0.saturating_add(rhs.0))
+        }
+    }
+}
+
+impl Semiring for MaxPlus {
+    fn zero() -> Self {
+        Self::NEG_INF
+    }
+
+    fn one() -> Self {
+        Self(0)
+    }
+}
+
+/// The boolean semiring `(or, and)`.
+///
+/// # Examples
+///
+/// ```
+/// use algorithm_rs::linalg::semiring::Boolean;
+///
+/// assert_eq!(Boolean(true) + Boolean(false), Boolean(true));
+/// assert_eq!(Boolean(true) * Boolean(false), Boolean(false));
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Default)]
+pub struct Boolean(pub bool);
+
+impl Add for Boolean {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self(self.0 || rhs.0)
+    }
+}
+
+impl Mul for Boolean {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        Self(self.0 && rhs.0)
+    }
+}
+
+impl Semiring for Boolean {
+    fn zero() -> Self {
+        Self(false)
+    }
+
+    fn one() -> Self {
+        Self(true)
+    }
+}
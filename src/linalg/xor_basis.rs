@@ -0,0 +1,395 @@
+//! Linear bases of integers under XOR, i.e. subspaces of GF(2)^BITS.
+//!
+//! [`XorBasis`] keeps its vectors fully reduced, so the maximum, the minimum
+//! and the `k`-th smallest element of the span all come from one greedy
+//! pass. [`PrefixXorBasis`] snapshots the basis of every prefix of an array,
+//! preferring the latest elements, which answers "maximum XOR of a subset
+//! of `a[l..r]`" online.
+
+use std::fmt::Debug;
+use std::hash::Hash;
+use std::ops::{BitXor, BitXorAssign, Range};
+
+/// Unsigned integers usable as vectors over GF(2).
+pub trait XorWord: Copy + Ord + Hash + Debug + BitXor<Output = Self> + BitXorAssign {
+    /// The number of bits.
+    const BITS: u32;
+
+    /// The zero vector.
+    const ZERO: Self;
+
+    /// Returns bit `i`.
+    fn bit(self, i: u32) -> bool;
+
+    /// Returns the index of the highest set bit, or `None` for zero.
+    fn top_bit(self) -> Option<u32>;
+}
+
+macro_rules! impl_xor_word {
+    ($($t:ty),*) => {
+        $(
+            impl XorWord for $t {
+                const BITS: u32 = <$t>::BITS;
+                const ZERO: Self = 0;
+
+                fn bit(self, i: u32) -> bool {
+                    self >> i & 1 == 1
+                }
+
+                fn top_bit(self) -> Option<u32> {
+                    self.checked_ilog2()
+                }
+            }
+        )*
+    };
+}
+
+impl_xor_word!(u8, u16, u32, u64, u128, usize);
+
+/// A basis of the XOR span of the inserted values.
+///
+/// Every basis vector has a distinct highest bit that is clear in all the
+/// others.
+///
+/// # Examples
+///
+/// ```
+/// use algorithm_rs::linalg::xor_basis::XorBasis;
+///
+/// let mut basis = XorBasis::new();
+/// for x in [0b1100u64, 0b1010, 0b0110, 0b0001] {
+///     basis.insert(x);
+/// }
+/// assert_eq!(basis.len(), 3);
+/// assert!(basis.contains(0b0111));
+/// assert_eq!(basis.max_xor(), 0b1101);
+/// assert_eq!(basis.min_xor(), Some(0b0001));
+/// // The span is {0, 1, 6, 7, 10, 11, 12, 13}.
+/// assert_eq!(basis.kth_smallest(4), Some(0b1010));
+/// ```
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct XorBasis<T> {
+    /// `vectors[b]` has highest bit `b`, or is zero if there is none.
+    vectors: Vec<T>,
+    len: usize,
+}
+
+impl<T: XorWord> XorBasis<T> {
+    /// Returns the basis of the zero space.
+    pub fn new() -> Self {
+        Self {
+            vectors: vec![T::ZERO; T::BITS as usize],
+            len: 0,
+        }
+    }
+
+    /// Returns the dimension of the span.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if only zero is spanned.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns `x` with every basis pivot bit cleared, which is zero exactly
+    /// when `x` is in the span.
+    pub fn reduce(&self, mut x: T) -> T {
+        for b in (0..T::BITS).rev() {
+            if x.bit(b) {
+                x ^= self.vectors[b as usize];
+            }
+        }
+        x
+    }
+
+    /// Adds `x` to the span, returning `true` if that made it larger.
+    pub fn insert(&mut self, x: T) -> bool {
+        let x = self.reduce(x);
+        let Some(b) = x.top_bit() else {
+            return false;
+        };
+        // x has no other pivot bits; clear bit b from the vectors above it.
+        for v in &mut self.vectors[b as usize + 1..] {
+            if v.bit(b) {
+                *v ^= x;
+            }
+        }
+        self.vectors[b as usize] = x;
+        self.len += 1;
+        true
+    }
+
+    /// Returns `true` if `x` is the XOR of some subset of the inserted values.
+    pub fn contains(&self, x: T) -> bool {
+        self.reduce(x) == T::ZERO
+    }
+
+    /// Adds the span of `other`.
+    pub fn merge(&mut self, other: &Self) {
+        for v in other.iter() {
+            self.insert(v);
+        }
+    }
+
+    /// Returns the basis vectors in increasing order.
+    pub fn iter(&self) -> impl Iterator<Item = T> + '_ {
+        self.vectors.iter().copied().filter(|&v| v != T::ZERO)
+    }
+
+    /// Returns the largest element of the span.
+    pub fn max_xor(&self) -> T {
+        self.iter().fold(T::ZERO, |acc, v| acc ^ v)
+    }
+
+    /// Returns the largest `x ^ s` over `s` in the span.
+    pub fn max_xor_with(&self, mut x: T) -> T {
+        for (b, &v) in self.vectors.iter().enumerate().rev() {
+            if v != T::ZERO && !x.bit(b as u32) {
+                x ^= v;
+            }
+        }
+        x
+    }
+
+    /// Returns the smallest nonzero element of the span.
+    pub fn min_xor(&self) -> Option<T> {
+        self.iter().next()
+    }
+
+    /// Returns the `k`-th smallest element of the span, counting zero as the
+    /// 0-th, or `None` if the span has at most `k` elements.
+    pub fn kth_smallest(&self, k: u128) -> Option<T> {
+        if self.len < 128 && k >> self.len != 0 {
+            return None;
+        }
+        // Bit i of k picks the i-th smallest basis vector.
+        Some(
+            self.iter()
+                .enumerate()
+                .filter(|&(i, _)| k >> i & 1 == 1)
+                .fold(T::ZERO, |acc, (_, v)| acc ^ v),
+        )
+    }
+}
+
+impl<T: XorWord> Default for XorBasis<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: XorWord> FromIterator<T> for XorBasis<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut basis = Self::new();
+        for x in iter {
+            basis.insert(x);
+        }
+        basis
+    }
+}
+
+/// The XOR bases of every prefix of a sequence, answering range queries
+/// online in `O(BITS)` with `O(n BITS)` memory.
+///
+/// Every push stores a snapshot of `BITS` vectors and `u32` positions, so
+/// each element costs `(size_of::<T>() + 4) * BITS` bytes: 256 for `u32`
+/// and 768 for `u64`, which is 768 MB for a million `u64` values.
+///
+/// Each snapshot keeps, for every highest bit, the vector built from the
+/// most recent elements, so the vectors whose oldest contribution lies
+/// inside `l..r` span exactly the subsets of `a[l..r]`.
+///
+/// # Examples
+///
+/// ```
+/// use algorithm_rs::linalg::xor_basis::PrefixXorBasis;
+///
+/// let a = [9u32, 4, 2, 12, 1];
+/// let prefix: PrefixXorBasis<u32> = a.iter().copied().collect();
+/// assert_eq!(prefix.max_xor(0..5), 15);
+/// assert_eq!(prefix.max_xor(1..4), 14);
+/// assert_eq!(prefix.max_xor(3..3), 0);
+/// ```
+#[derive(Clone, Debug)]
+pub struct PrefixXorBasis<T> {
+    /// The current basis, one slot per highest bit.
+    vectors: Vec<T>,
+    /// The position of the oldest element each slot depends on.
+    positions: Vec<u32>,
+    /// Snapshots of `vectors` and `positions` after each push.
+    vector_history: Vec<T>,
+    position_history: Vec<u32>,
+}
+
+impl<T: XorWord> PrefixXorBasis<T> {
+    /// Returns the structure for the empty sequence.
+    pub fn new() -> Self {
+        Self {
+            vectors: vec![T::ZERO; T::BITS as usize],
+            positions: vec![0; T::BITS as usize],
+            vector_history: Vec::new(),
+            position_history: Vec::new(),
+        }
+    }
+
+    /// Returns the number of elements pushed.
+    pub fn len(&self) -> usize {
+        self.position_history.len() / T::BITS as usize
+    }
+
+    /// Returns `true` if nothing has been pushed.
+    pub fn is_empty(&self) -> bool {
+        self.position_history.is_empty()
+    }
+
+    /// Appends `x` to the sequence.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the sequence already holds `u32::MAX` elements.
+    pub fn push(&mut self, mut x: T) {
+        let mut pos = u32::try_from(self.len()).expect("too many elements");
+        while let Some(b) = x.top_bit() {
+            let b = b as usize;
+            if self.vectors[b] == T::ZERO {
+                self.vectors[b] = x;
+                self.positions[b] = pos;
+                break;
+            }
+            // Keep the newer vector in the slot and push the older one down.
+            if self.positions[b] < pos {
+                std::mem::swap(&mut self.vectors[b], &mut x);
+                std::mem::swap(&mut self.positions[b], &mut pos);
+            }
+            x ^= self.vectors[b];
+        }
+        self.vector_history.extend_from_slice(&self.vectors);
+        self.position_history.extend_from_slice(&self.positions);
+    }
+
+    /// Returns the largest XOR of a subset of the elements in `range`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `range.end` exceeds the length.
+    pub fn max_xor(&self, range: Range<usize>) -> T {
+        assert!(range.end <= self.len(), "range out of bounds");
+        if range.start >= range.end {
+            return T::ZERO;
+        }
+        let bits = T::BITS as usize;
+        let offset = (range.end - 1) * bits;
+        let vectors = &self.vector_history[offset..offset + bits];
+        let positions = &self.position_history[offset..offset + bits];
+
+        let mut result = T::ZERO;
+        for b in (0..bits).rev() {
+            if positions[b] as usize >= range.start
+                && vectors[b] != T::ZERO
+                && !result.bit(b as u32)
+            {
+                result ^= vectors[b];
+            }
+        }
+        result
+    }
+}
+
+impl<T: XorWord> Default for PrefixXorBasis<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: XorWord> FromIterator<T> for PrefixXorBasis<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut prefix = Self::new();
+        for x in iter {
+            prefix.push(x);
+        }
+        prefix
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::Rng;
+    use std::collections::BTreeSet;
+
+    /// Returns every XOR of a subset of `values`.
+    fn span(values: &[u16]) -> BTreeSet<u16> {
+        let mut set = BTreeSet::from([0]);
+        for &x in values {
+            let shifted: Vec<u16> = set.iter().map(|&s| s ^ x).collect();
+            set.extend(shifted);
+        }
+        set
+    }
+
+    #[test]
+    fn test_against_brute_force_span() {
+        let mut rng = Rng(250);
+        for _ in 0..300 {
+            let n = (rng.next() % 8) as usize;
+            // Few distinct bits so that dependent values are common.
+            let mask = rng.next() as u16 & 0x0f3c;
+            let values: Vec<u16> = (0..n).map(|_| rng.next() as u16 & mask).collect();
+            let set = span(&values);
+            let sorted: Vec<u16> = set.iter().copied().collect();
+
+            let mut basis = XorBasis::new();
+            let mut rank = 0;
+            for &x in &values {
+                rank += basis.insert(x) as usize;
+            }
+            assert_eq!(basis.len(), rank);
+            assert_eq!(1usize << rank, set.len());
+            assert_eq!(basis.max_xor(), *sorted.last().unwrap());
+            assert_eq!(basis.min_xor(), sorted.get(1).copied());
+            for (k, &x) in sorted.iter().enumerate() {
+                assert_eq!(basis.kth_smallest(k as u128), Some(x));
+            }
+            assert_eq!(basis.kth_smallest(sorted.len() as u128), None);
+
+            for _ in 0..20 {
+                let x = rng.next() as u16;
+                assert_eq!(basis.contains(x), set.contains(&x));
+                let best = set.iter().map(|&s| s ^ x).max().unwrap();
+                assert_eq!(basis.max_xor_with(x), best);
+            }
+
+            let other: XorBasis<u16> = (0..3).map(|_| rng.next() as u16 & 0x00ff).collect();
+            let mut merged = basis.clone();
+            merged.merge(&other);
+            let all: Vec<u16> = values.iter().copied().chain(other.iter()).collect();
+            assert_eq!(1usize << merged.len(), span(&all).len());
+            assert_eq!(merged.max_xor(), *span(&all).last().unwrap());
+        }
+
+        let full: XorBasis<u128> = (0..128).map(|i| 1u128 << i).collect();
+        assert_eq!(full.kth_smallest(u128::MAX), Some(u128::MAX));
+        assert_eq!(full.max_xor_with(5), u128::MAX);
+    }
+
+    #[test]
+    fn test_prefix_basis_ranges() {
+        let mut rng = Rng(251);
+        let a: Vec<u16> = (0..60).map(|_| rng.next() as u16 & 0x3ff).collect();
+        let prefix: PrefixXorBasis<u16> = a.iter().copied().collect();
+        assert_eq!(prefix.len(), a.len());
+        for l in 0..=a.len() {
+            // The span of a[l..r], grown one element at a time.
+            let mut set = BTreeSet::from([0]);
+            for r in l..=a.len() {
+                assert_eq!(prefix.max_xor(l..r), *set.last().unwrap(), "{}..{}", l, r);
+                if r < a.len() {
+                    let shifted: Vec<u16> = set.iter().map(|&s| s ^ a[r]).collect();
+                    set.extend(shifted);
+                }
+            }
+        }
+    }
+}
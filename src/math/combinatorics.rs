@@ -0,0 +1,341 @@
+//! Binomial coefficients and related counts modulo primes and prime powers.
+
+use crate::math::modint::ModIntLike;
+
+/// Factorial and inverse factorial tables that grow on demand.
+///
+/// The tables only reach `modulus - 1`, so arguments at or beyond the
+/// modulus panic; use [`Binomial::lucas`] for those with a small prime
+/// modulus and [`BinomialPrimePower`] for prime powers.
+///
+/// # Examples
+///
+/// ```
+/// use algorithm_rs::math::combinatorics::Binomial;
+/// use algorithm_rs::math::modint::ModInt998244353 as Mint;
+///
+/// let mut binom = Binomial::<Mint>::new();
+/// assert_eq!(binom.c(5, 2).val(), 10);
+/// assert_eq!(binom.h(3, 2).val(), 6);
+/// assert_eq!(binom.catalan(4).val(), 14);
+/// assert_eq!(binom.c(2, 5).val(), 0);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Binomial<M> {
+    fact: Vec<M>,
+    inv_fact: Vec<M>,
+}
+
+impl<M: ModIntLike> Binomial<M> {
+    /// Creates empty tables.
+    pub fn new() -> Self {
+        Self {
+            fact: vec![M::one()],
+            inv_fact: vec![M::one()],
+        }
+    }
+
+    /// Creates tables covering `0..=n` up front.
+    pub fn with_capacity(n: usize) -> Self {
+        let mut binom = Self::new();
+        binom.ensure(n);
+        binom
+    }
+
+    /// Grows the tables to cover `n`, at least doubling them.
+    fn ensure(&mut self, n: usize) {
+        let len = self.fact.len();
+        if n < len {
+            return;
+        }
+
+        let m = M::modulus() as usize;
+        assert!(n < m, "{} is not below the modulus {}", n, m);
+        let new_len = (2 * len).max(n + 1).min(m);
+        for i in len..new_len {
+            let f = self.fact[i - 1] * M::from(i);
+            self.fact.push(f);
+        }
+        self.inv_fact.resize(new_len, M::zero());
+        self.inv_fact[new_len - 1] = self.fact[new_len - 1].inv();
+        for i in (len..new_len - 1).rev() {
+            self.inv_fact[i] = self.inv_fact[i + 1] * M::from(i + 1);
+        }
+    }
+
+    /// Returns `n!`.
+    pub fn fact(&mut self, n: usize) -> M {
+        self.ensure(n);
+        self.fact[n]
+    }
+
+    /// Returns `1 / n!`.
+    pub fn inv_fact(&mut self, n: usize) -> M {
+        self.ensure(n);
+        self.inv_fact[n]
+    }
+
+    /// Returns `1 / n`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is zero.
+    pub fn inv(&mut self, n: usize) -> M {
+        assert!(n > 0, "zero has no inverse");
+        self.ensure(n);
+        self.inv_fact[n] * self.fact[n - 1]
+    }
+
+    /// Returns the number of `k`-subsets of an `n`-set.
+    pub fn c(&mut self, n: usize, k: usize) -> M {
+        if k > n {
+            return M::zero();
+        }
+        self.ensure(n);
+        self.fact[n] * self.inv_fact[k] * self.inv_fact[n - k]
+    }
+
+    /// Returns the number of `k`-permutations of an `n`-set.
+    pub fn p(&mut self, n: usize, k: usize) -> M {
+        if k > n {
+            return M::zero();
+        }
+        self.ensure(n);
+        self.fact[n] * self.inv_fact[n - k]
+    }
+
+    /// Returns the number of `k`-multisets of an `n`-set.
+    pub fn h(&mut self, n: usize, k: usize) -> M {
+        if n == 0 {
+            return if k == 0 { M::one() } else { M::zero() };
+        }
+        self.c(n + k - 1, k)
+    }
+
+    /// Returns the `n`-th Catalan number.
+    pub fn catalan(&mut self, n: usize) -> M {
+        self.ensure((2 * n).max(n + 1));
+        self.fact[2 * n] * self.inv_fact[n] * self.inv_fact[n + 1]
+    }
+
+    /// Returns the number of arrangements of a multiset with multiplicities `ks`.
+    pub fn multinomial(&mut self, ks: &[usize]) -> M {
+        let n = ks.iter().sum();
+        self.ensure(n);
+        ks.iter()
+            .fold(self.fact[n], |acc, &k| acc * self.inv_fact[k])
+    }
+
+    /// Returns `C(n, k)` for arbitrarily large arguments by Lucas' theorem.
+    ///
+    /// The modulus must be prime; the tables grow up to its size, so it
+    /// should be small.
+    pub fn lucas(&mut self, mut n: u64, mut k: u64) -> M {
+        let p = M::modulus() as u64;
+        let mut result = M::one();
+        while k > 0 {
+            let (ni, ki) = ((n % p) as usize, (k % p) as usize);
+            if ki > ni {
+                return M::zero();
+            }
+            result *= self.c(ni, ki);
+            n /= p;
+            k /= p;
+        }
+
+        result
+    }
+}
+
+impl<M: ModIntLike> Default for Binomial<M> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Binomial coefficients modulo a prime power `p^e`, by Granville's
+/// generalization of Lucas' theorem.
+///
+/// Construction builds a table of size `p^e`, which should therefore stay
+/// around `10^7` or below. Arbitrary moduli can be handled by combining the
+/// prime power factors with the Chinese remainder theorem.
+///
+/// # Examples
+///
+/// ```
+/// use algorithm_rs::math::combinatorics::BinomialPrimePower;
+///
+/// let binom = BinomialPrimePower::new(3, 2);
+/// assert_eq!(binom.c(10, 3), 120 % 9);
+/// assert_eq!(binom.c(1_000_000_000_000, 1), 1_000_000_000_000 % 9);
+/// ```
+#[derive(Clone, Debug)]
+pub struct BinomialPrimePower {
+    p: u64,
+    e: u32,
+    q: u64,
+    /// `table[i]` is the product of the `j <= i` coprime to `p`, modulo `q`.
+    table: Vec<u64>,
+}
+
+impl BinomialPrimePower {
+    /// Prepares binomials modulo `p^e`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `p < 2`, `e == 0` or `p^e` does not fit in `u32`.
+    pub fn new(p: u32, e: u32) -> Self {
+        assert!(p >= 2 && e >= 1, "invalid prime power");
+        let p = p as u64;
+        let q = p
+            .checked_pow(e)
+            .filter(|&q| q <= u32::MAX as u64)
+            .expect("modulus must fit in u32");
+
+        let mut table = vec![1 % q; q as usize];
+        for i in 1..q as usize {
+            let factor = if (i as u64).is_multiple_of(p) {
+                1
+            } else {
+                i as u64
+            };
+            table[i] = table[i - 1] * factor % q;
+        }
+
+        Self { p, e, q, table }
+    }
+
+    /// Returns the modulus `p^e`.
+    pub fn modulus(&self) -> u64 {
+        self.q
+    }
+
+    /// Returns `C(n, k)` modulo `p^e`.
+    pub fn c(&self, n: u64, k: u64) -> u64 {
+        if k > n {
+            return 0;
+        }
+        let r = n - k;
+
+        let v = self.valuation(n) - self.valuation(k) - self.valuation(r);
+        if v >= self.e as u64 {
+            return 0;
+        }
+
+        let den = self.unit_part(k) * self.unit_part(r) % self.q;
+        let num = self.unit_part(n) * inv_mod(den, self.q) % self.q;
+        num * self.p.pow(v as u32) % self.q
+    }
+
+    /// Returns the exponent of `p` in `n!`.
+    fn valuation(&self, mut n: u64) -> u64 {
+        let mut v = 0;
+        while n > 0 {
+            n /= self.p;
+            v += n;
+        }
+        v
+    }
+
+    /// Returns `n! / p^valuation(n)` modulo `q`.
+    fn unit_part(&self, mut n: u64) -> u64 {
+        // The full blocks contribute table[q - 1] = +-1 each.
+        let full = self.table[self.q as usize - 1];
+        let mut result = 1 % self.q;
+        while n > 0 {
+            result = result * self.table[(n % self.q) as usize] % self.q;
+            if (n / self.q) % 2 == 1 {
+                result = result * full % self.q;
+            }
+            n /= self.p;
+        }
+        result
+    }
+}
+
+/// Returns the inverse of `a` modulo `m`, for `a` coprime to `m`.
+fn inv_mod(a: u64, m: u64) -> u64 {
+    let (mut r0, mut r1) = (a as i64, m as i64);
+    let (mut x0, mut x1) = (1i64, 0i64);
+    while r1 != 0 {
+        let t = r0 / r1;
+        (r0, r1) = (r1, r0 - t * r1);
+        (x0, x1) = (x1, x0 - t * x1);
+    }
+    x0.rem_euclid(m as i64) as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::modint::{ModInt998244353, StaticModInt};
+
+    fn pascal(n: usize, m: u64) -> Vec<Vec<u64>> {
+        let mut c = vec![vec![0; n + 1]; n + 1];
+        for i in 0..=n {
+            c[i][0] = 1 % m;
+            for j in 1..=i {
+                c[i][j] = (c[i - 1][j - 1] + c[i - 1][j]) % m;
+            }
+        }
+        c
+    }
+
+    #[test]
+    fn test_binomial() {
+        let mut binom = Binomial::<ModInt998244353>::new();
+        let c = pascal(300, 998244353);
+        for (n, row) in c.iter().enumerate() {
+            for (k, &expected) in row[..=n].iter().enumerate() {
+                assert_eq!(binom.c(n, k).val() as u64, expected);
+            }
+        }
+
+        assert_eq!(binom.p(5, 3).val(), 60);
+        assert_eq!(binom.h(0, 0).val(), 1);
+        assert_eq!(binom.h(0, 3).val(), 0);
+        assert_eq!(binom.h(4, 3).val(), 20);
+        assert_eq!(binom.multinomial(&[2, 1, 1]).val(), 12);
+        assert_eq!(
+            binom.inv(7) * ModInt998244353::new(7),
+            ModInt998244353::one()
+        );
+        let catalan: Vec<u32> = (0..8).map(|n| binom.catalan(n).val()).collect();
+        assert_eq!(catalan, vec![1, 1, 2, 5, 14, 42, 132, 429]);
+        // Fresh tables must grow far enough for the small cases too.
+        assert_eq!(Binomial::<ModInt998244353>::new().catalan(0).val(), 1);
+        assert_eq!(Binomial::<ModInt998244353>::new().catalan(1).val(), 1);
+    }
+
+    #[test]
+    fn test_lucas() {
+        let mut binom = Binomial::<StaticModInt<7>>::new();
+        let c = pascal(400, 7);
+        for (n, row) in c.iter().enumerate() {
+            for (k, &expected) in row[..=n].iter().enumerate() {
+                assert_eq!(binom.lucas(n as u64, k as u64).val() as u64, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_prime_power() {
+        for &(p, e) in &[(2, 1), (2, 5), (3, 3), (5, 2), (7, 1), (13, 2)] {
+            let binom = BinomialPrimePower::new(p, e);
+            let c = pascal(300, binom.modulus());
+            for (n, row) in c.iter().enumerate() {
+                for (k, &expected) in row[..=n].iter().enumerate() {
+                    assert_eq!(
+                        binom.c(n as u64, k as u64),
+                        expected,
+                        "{}^{} C({}, {})",
+                        p,
+                        e,
+                        n,
+                        k
+                    );
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,577 @@
+//! Convolution of sequences via the number theoretic transform.
+//!
+//! [`convolution`] works over any [`ModIntLike`] type: NTT-friendly prime
+//! moduli such as 998244353 use a radix-4 NTT directly, other moduli go
+//! through three NTT primes and the Chinese remainder theorem.
+
+use crate::math::modint::{ModIntLike, StaticModInt};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+/// Inputs with a side at most this long are multiplied naively.
+const NAIVE_THRESHOLD: usize = 60;
+
+const MOD1: u32 = 754_974_721; // 2^24 * 45 + 1
+const MOD2: u32 = 167_772_161; // 2^25 * 5 + 1
+const MOD3: u32 = 469_762_049; // 2^26 * 7 + 1
+
+/// The longest result the three primes can all transform, set by `MOD1`.
+const MAX_CRT_LEN: usize = 1 << 24;
+
+/// Roots of unity for one NTT-friendly prime, as raw residues.
+struct NttInfo {
+    rank2: usize,
+    root: Vec<u32>,
+    rate2: Vec<u32>,
+    irate2: Vec<u32>,
+    rate3: Vec<u32>,
+    irate3: Vec<u32>,
+}
+
+impl NttInfo {
+    fn new<T: ModIntLike>() -> Option<Self> {
+        let m = T::modulus();
+        if m < 3 || !is_prime_u32(m) {
+            return None;
+        }
+
+        let rank2 = (m - 1).trailing_zeros() as usize;
+        let g = T::new(primitive_root_u32(m));
+        let mut root = vec![T::zero(); rank2 + 1];
+        let mut iroot = vec![T::zero(); rank2 + 1];
+        root[rank2] = g.pow(((m - 1) >> rank2) as u64);
+        iroot[rank2] = root[rank2].inv();
+        for i in (0..rank2).rev() {
+            root[i] = root[i + 1] * root[i + 1];
+            iroot[i] = iroot[i + 1] * iroot[i + 1];
+        }
+
+        let rates = |step: usize| {
+            let (mut rate, mut irate) = (Vec::new(), Vec::new());
+            let (mut prod, mut iprod) = (T::one(), T::one());
+            for i in 0..(rank2 + 1).saturating_sub(step) {
+                rate.push((root[i + step] * prod).val());
+                irate.push((iroot[i + step] * iprod).val());
+                prod *= iroot[i + step];
+                iprod *= root[i + step];
+            }
+            (rate, irate)
+        };
+        let (rate2, irate2) = rates(2);
+        let (rate3, irate3) = rates(3);
+
+        Some(Self {
+            rank2,
+            root: root.iter().map(|x| x.val()).collect(),
+            rate2,
+            irate2,
+            rate3,
+            irate3,
+        })
+    }
+}
+
+thread_local! {
+    static NTT_INFO: RefCell<HashMap<u32, Option<Rc<NttInfo>>>> = RefCell::new(HashMap::new());
+}
+
+fn ntt_info<T: ModIntLike>() -> Option<Rc<NttInfo>> {
+    NTT_INFO.with(|cache| {
+        cache
+            .borrow_mut()
+            .entry(T::modulus())
+            .or_insert_with(|| NttInfo::new::<T>().map(Rc::new))
+            .clone()
+    })
+}
+
+fn is_prime_u32(n: u32) -> bool {
+    if n < 2 {
+        return false;
+    }
+    for p in [2, 3, 5, 7] {
+        if n.is_multiple_of(p) {
+            return n == p;
+        }
+    }
+
+    let pow = |mut a: u64, mut e: u64| {
+        let mut result = 1;
+        while e > 0 {
+            if e & 1 == 1 {
+                result = result * a % n as u64;
+            }
+            a = a * a % n as u64;
+            e >>= 1;
+        }
+        result
+    };
+
+    let d = (n - 1) >> (n - 1).trailing_zeros();
+    [2, 7, 61].iter().all(|&a| {
+        if a % n as u64 == 0 {
+            return true;
+        }
+        let mut t = d as u64;
+        let mut y = pow(a, t);
+        while t != (n - 1) as u64 && y != 1 && y != (n - 1) as u64 {
+            y = y * y % n as u64;
+            t <<= 1;
+        }
+        y == (n - 1) as u64 || t & 1 == 1
+    })
+}
+
+fn primitive_root_u32(m: u32) -> u32 {
+    let mut factors = Vec::new();
+    let mut x = m - 1;
+    let mut p = 2;
+    while p * p <= x {
+        if x.is_multiple_of(p) {
+            factors.push(p);
+            while x.is_multiple_of(p) {
+                x /= p;
+            }
+        }
+        p += 1;
+    }
+    if x > 1 {
+        factors.push(x);
+    }
+
+    let pow = |mut a: u64, mut e: u64| {
+        let mut result = 1;
+        while e > 0 {
+            if e & 1 == 1 {
+                result = result * a % m as u64;
+            }
+            a = a * a % m as u64;
+            e >>= 1;
+        }
+        result
+    };
+
+    (2..)
+        .find(|&g| {
+            factors
+                .iter()
+                .all(|&q| pow(g as u64, ((m - 1) / q) as u64) != 1)
+        })
+        .unwrap()
+}
+
+/// Returns `true` if the modulus of `T` admits an NTT of length `len`.
+///
+/// This holds when the modulus is a prime `p` and `len` divides a power of
+/// two that divides `p - 1`.
+pub fn supports_ntt<T: ModIntLike>(len: usize) -> bool {
+    ntt_info::<T>()
+        .is_some_and(|info| len.next_power_of_two().trailing_zeros() as usize <= info.rank2)
+}
+
+fn expect_info<T: ModIntLike>(h: usize) -> Rc<NttInfo> {
+    ntt_info::<T>()
+        .filter(|info| h <= info.rank2)
+        .unwrap_or_else(|| {
+            panic!(
+                "modulus {} does not support an NTT of length 2^{}",
+                T::modulus(),
+                h
+            )
+        })
+}
+
+/// Transforms `a` in place into its NTT, with the output in bit-reversed order.
+///
+/// # Panics
+///
+/// Panics if `a.len()` is not a power of two or if [`supports_ntt`] fails.
+pub fn ntt<T: ModIntLike>(a: &mut [T]) {
+    let n = a.len();
+    assert!(n.is_power_of_two(), "length must be a power of two");
+    let h = n.trailing_zeros() as usize;
+    let info = expect_info::<T>(h);
+
+    // a[i, i + (n >> len), i + 2 * (n >> len), ..] is transformed.
+    let mut len = 0;
+    while len < h {
+        if h - len == 1 {
+            let p = 1 << (h - len - 1);
+            let mut rot = T::one();
+            for s in 0..1 << len {
+                let offset = s << (h - len);
+                for i in offset..offset + p {
+                    let l = a[i];
+                    let r = a[i + p] * rot;
+                    a[i] = l + r;
+                    a[i + p] = l - r;
+                }
+                if s + 1 != 1 << len {
+                    rot *= T::raw(info.rate2[(!s).trailing_zeros() as usize]);
+                }
+            }
+            len += 1;
+        } else {
+            let p = 1 << (h - len - 2);
+            let imag = T::raw(info.root[2]);
+            let mut rot = T::one();
+            for s in 0..1 << len {
+                let rot2 = rot * rot;
+                let rot3 = rot2 * rot;
+                let offset = s << (h - len);
+                for i in offset..offset + p {
+                    let a0 = a[i];
+                    let a1 = a[i + p] * rot;
+                    let a2 = a[i + 2 * p] * rot2;
+                    let a3 = a[i + 3 * p] * rot3;
+                    let a1na3imag = (a1 - a3) * imag;
+                    a[i] = a0 + a2 + a1 + a3;
+                    a[i + p] = a0 + a2 - (a1 + a3);
+                    a[i + 2 * p] = a0 - a2 + a1na3imag;
+                    a[i + 3 * p] = a0 - a2 - a1na3imag;
+                }
+                if s + 1 != 1 << len {
+                    rot *= T::raw(info.rate3[(!s).trailing_zeros() as usize]);
+                }
+            }
+            len += 2;
+        }
+    }
+}
+
+/// Inverts [`ntt`] in place, including the division by `a.len()`.
+///
+/// # Panics
+///
+/// Panics if `a.len()` is not a power of two or if [`supports_ntt`] fails.
+pub fn intt<T: ModIntLike>(a: &mut [T]) {
+    let n = a.len();
+    assert!(n.is_power_of_two(), "length must be a power of two");
+    let h = n.trailing_zeros() as usize;
+    let info = expect_info::<T>(h);
+
+    let mut len = h;
+    while len > 0 {
+        if len == 1 {
+            let p = 1 << (h - len);
+            let mut irot = T::one();
+            for s in 0..1 << (len - 1) {
+                let offset = s << (h - len + 1);
+                for i in offset..offset + p {
+                    let l = a[i];
+                    let r = a[i + p];
+                    a[i] = l + r;
+                    a[i + p] = (l - r) * irot;
+                }
+                if s + 1 != 1 << (len - 1) {
+                    irot *= T::raw(info.irate2[(!s).trailing_zeros() as usize]);
+                }
+            }
+            len -= 1;
+        } else {
+            let p = 1 << (h - len);
+            let iimag = T::raw(info.root[2]).inv();
+            let mut irot = T::one();
+            for s in 0..1 << (len - 2) {
+                let irot2 = irot * irot;
+                let irot3 = irot2 * irot;
+                let offset = s << (h - len + 2);
+                for i in offset..offset + p {
+                    let a0 = a[i];
+                    let a1 = a[i + p];
+                    let a2 = a[i + 2 * p];
+                    let a3 = a[i + 3 * p];
+                    let a2na3iimag = (a2 - a3) * iimag;
+                    a[i] = a0 + a1 + a2 + a3;
+                    a[i + p] = (a0 - a1 + a2na3iimag) * irot;
+                    a[i + 2 * p] = (a0 + a1 - a2 - a3) * irot2;
+                    a[i + 3 * p] = (a0 - a1 - a2na3iimag) * irot3;
+                }
+                if s + 1 != 1 << (len - 2) {
+                    irot *= T::raw(info.irate3[(!s).trailing_zeros() as usize]);
+                }
+            }
+            len -= 2;
+        }
+    }
+
+    let inv_n = T::from(n).inv();
+    for x in a.iter_mut() {
+        *x *= inv_n;
+    }
+}
+
+fn convolution_naive<T: ModIntLike>(a: &[T], b: &[T]) -> Vec<T> {
+    let mut c = vec![T::zero(); a.len() + b.len() - 1];
+    for (i, &x) in a.iter().enumerate() {
+        for (j, &y) in b.iter().enumerate() {
+            c[i + j] += x * y;
+        }
+    }
+
+    c
+}
+
+/// Returns the product of the polynomials `a` and `b`.
+///
+/// Moduli that do not support an NTT of the required length fall back to
+/// [`convolution_arbitrary`].
+///
+/// # Panics
+///
+/// Panics if that fallback is needed and the result is longer than `2^24`.
+///
+/// # Examples
+///
+/// ```
+/// use algorithm_rs::math::convolution::convolution;
+/// use algorithm_rs::math::modint::ModInt998244353 as Mint;
+///
+/// let a: Vec<Mint> = [1, 2, 3].iter().map(|&x| Mint::new(x)).collect();
+/// let b: Vec<Mint> = [4, 5].iter().map(|&x| Mint::new(x)).collect();
+/// let c: Vec<u32> = convolution(&a, &b).iter().map(|x| x.val()).collect();
+/// assert_eq!(c, vec![4, 13, 22, 15]);
+/// ```
+pub fn convolution<T: ModIntLike>(a: &[T], b: &[T]) -> Vec<T> {
+    if a.is_empty() || b.is_empty() {
+        return Vec::new();
+    }
+    if a.len().min(b.len()) <= NAIVE_THRESHOLD {
+        return convolution_naive(a, b);
+    }
+
+    let len = a.len() + b.len() - 1;
+    let z = len.next_power_of_two();
+    if !supports_ntt::<T>(z) {
+        // The fallback convolves over these very primes and would recurse.
+        assert!(
+            ![MOD1, MOD2, MOD3].contains(&T::modulus()),
+            "modulus {} does not support an NTT of length {}",
+            T::modulus(),
+            z
+        );
+        return convolution_arbitrary(a, b);
+    }
+
+    let mut fa = a.to_vec();
+    let mut fb = b.to_vec();
+    fa.resize(z, T::zero());
+    fb.resize(z, T::zero());
+    ntt(&mut fa);
+    ntt(&mut fb);
+    for (x, y) in fa.iter_mut().zip(&fb) {
+        *x *= *y;
+    }
+    intt(&mut fa);
+    fa.truncate(len);
+
+    fa
+}
+
+fn convolution_residues<const P: u32>(a: &[i128], b: &[i128]) -> Vec<u32> {
+    let fa: Vec<StaticModInt<P>> = a.iter().map(|&x| StaticModInt::new(x)).collect();
+    let fb: Vec<StaticModInt<P>> = b.iter().map(|&x| StaticModInt::new(x)).collect();
+
+    convolution(&fa, &fb)
+        .into_iter()
+        .map(StaticModInt::val)
+        .collect()
+}
+
+/// Returns the convolution of `a` and `b` reduced into `0..MOD1 * MOD2 * MOD3`.
+fn convolution_crt(a: &[i128], b: &[i128]) -> Vec<u128> {
+    let len = a.len() + b.len() - 1;
+    assert!(
+        len <= MAX_CRT_LEN,
+        "convolution length {} exceeds the limit 2^24",
+        len
+    );
+
+    type Mint2 = StaticModInt<MOD2>;
+    type Mint3 = StaticModInt<MOD3>;
+
+    let c1 = convolution_residues::<MOD1>(a, b);
+    let c2 = convolution_residues::<MOD2>(a, b);
+    let c3 = convolution_residues::<MOD3>(a, b);
+
+    let m1 = MOD1 as u128;
+    let m1m2 = m1 * MOD2 as u128;
+    let inv_m1_mod2 = Mint2::new(MOD1).inv();
+    let inv_m1m2_mod3 = Mint3::new(m1m2).inv();
+
+    c1.iter()
+        .zip(&c2)
+        .zip(&c3)
+        .map(|((&x1, &r2), &r3)| {
+            let x2 = ((Mint2::new(r2) - x1) * inv_m1_mod2).val();
+            let x3 = ((Mint3::new(r3) - x1 - Mint3::new(x2) * MOD1) * inv_m1m2_mod3).val();
+            x1 as u128 + x2 as u128 * m1 + x3 as u128 * m1m2
+        })
+        .collect()
+}
+
+/// Returns the product of `a` and `b` for any modulus, via three NTT primes.
+///
+/// The result has at most `2^24` elements, the longest NTT all three
+/// primes support. It is exact when `min(a.len(), b.len()) (m - 1)^2` stays
+/// below their product, about `5.9 * 10^25`; for any `u32` modulus `m` a
+/// shorter side of under `2^20` elements suffices.
+///
+/// # Panics
+///
+/// Panics if `a.len() + b.len() - 1 > 2^24`.
+pub fn convolution_arbitrary<T: ModIntLike>(a: &[T], b: &[T]) -> Vec<T> {
+    if a.is_empty() || b.is_empty() {
+        return Vec::new();
+    }
+
+    let fa: Vec<i128> = a.iter().map(|x| x.val() as i128).collect();
+    let fb: Vec<i128> = b.iter().map(|x| x.val() as i128).collect();
+
+    convolution_crt(&fa, &fb).into_iter().map(T::new).collect()
+}
+
+/// Returns the exact convolution of `a` and `b` over the integers.
+///
+/// Every coefficient of the result must fit in `i64`.
+///
+/// # Panics
+///
+/// Panics if `a.len() + b.len() - 1 > 2^24`.
+///
+/// # Examples
+///
+/// ```
+/// use algorithm_rs::math::convolution::convolution_i64;
+///
+/// assert_eq!(convolution_i64(&[1, -2], &[-3, 1_000_000_000_000]), vec![-3, 1_000_000_000_006, -2_000_000_000_000]);
+/// ```
+pub fn convolution_i64(a: &[i64], b: &[i64]) -> Vec<i64> {
+    if a.is_empty() || b.is_empty() {
+        return Vec::new();
+    }
+
+    let fa: Vec<i128> = a.iter().map(|&x| x as i128).collect();
+    let fb: Vec<i128> = b.iter().map(|&x| x as i128).collect();
+    let m = MOD1 as u128 * MOD2 as u128 * MOD3 as u128;
+
+    convolution_crt(&fa, &fb)
+        .into_iter()
+        .map(|x| {
+            if x < m / 2 {
+                x as i64
+            } else {
+                (x as i128 - m as i128) as i64
+            }
+        })
+        .collect()
+}
+
+/// Returns the exact convolution of `a` and `b` over the non-negative integers.
+///
+/// Every coefficient of the result must fit in `u64`.
+///
+/// # Panics
+///
+/// Panics if `a.len() + b.len() - 1 > 2^24`.
+pub fn convolution_u64(a: &[u64], b: &[u64]) -> Vec<u64> {
+    if a.is_empty() || b.is_empty() {
+        return Vec::new();
+    }
+
+    let fa: Vec<i128> = a.iter().map(|&x| x as i128).collect();
+    let fb: Vec<i128> = b.iter().map(|&x| x as i128).collect();
+
+    convolution_crt(&fa, &fb)
+        .into_iter()
+        .map(|x| x as u64)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::modint::{ModInt1000000007, ModInt998244353};
+    use crate::test_util::Rng;
+
+    fn random_vec<T: ModIntLike>(rng: &mut Rng, n: usize) -> Vec<T> {
+        (0..n).map(|_| T::new(rng.next())).collect()
+    }
+
+    #[test]
+    fn test_ntt_roundtrip() {
+        let mut rng = Rng(1);
+        for h in 0..12 {
+            let a: Vec<ModInt998244353> = random_vec(&mut rng, 1 << h);
+            let mut b = a.clone();
+            ntt(&mut b);
+            intt(&mut b);
+            assert_eq!(a, b);
+        }
+    }
+
+    #[test]
+    fn test_convolution_ntt_friendly() {
+        let mut rng = Rng(2);
+        for &(n, m) in &[
+            (1, 1),
+            (61, 61),
+            (100, 1),
+            (61, 200),
+            (256, 257),
+            (1000, 777),
+        ] {
+            let a: Vec<ModInt998244353> = random_vec(&mut rng, n);
+            let b: Vec<ModInt998244353> = random_vec(&mut rng, m);
+            assert_eq!(convolution(&a, &b), convolution_naive(&a, &b));
+        }
+        assert!(convolution::<ModInt998244353>(&[], &[ModInt998244353::new(1)]).is_empty());
+    }
+
+    #[test]
+    fn test_convolution_arbitrary() {
+        let mut rng = Rng(3);
+        for &(n, m) in &[(1, 5), (70, 80), (500, 300)] {
+            let a: Vec<ModInt1000000007> = random_vec(&mut rng, n);
+            let b: Vec<ModInt1000000007> = random_vec(&mut rng, m);
+            assert_eq!(convolution(&a, &b), convolution_naive(&a, &b));
+            assert_eq!(convolution_arbitrary(&a, &b), convolution_naive(&a, &b));
+        }
+    }
+
+    #[test]
+    fn test_convolution_integers() {
+        let mut rng = Rng(4);
+        for &(n, m) in &[(1, 1), (100, 100), (300, 71)] {
+            let a: Vec<i64> = (0..n).map(|_| rng.next() as i64 >> 44).collect();
+            let b: Vec<i64> = (0..m).map(|_| rng.next() as i64 >> 44).collect();
+            let mut expected = vec![0i64; n + m - 1];
+            for i in 0..n {
+                for j in 0..m {
+                    expected[i + j] += a[i] * b[j];
+                }
+            }
+            assert_eq!(convolution_i64(&a, &b), expected);
+
+            let a: Vec<u64> = a.iter().map(|&x| x.unsigned_abs() << 4).collect();
+            let b: Vec<u64> = b.iter().map(|&x| x.unsigned_abs() << 4).collect();
+            let mut expected = vec![0u64; n + m - 1];
+            for i in 0..n {
+                for j in 0..m {
+                    expected[i + j] += a[i] * b[j];
+                }
+            }
+            assert_eq!(convolution_u64(&a, &b), expected);
+        }
+    }
+
+    #[test]
+    fn test_supports_ntt() {
+        assert!(supports_ntt::<ModInt998244353>(1 << 23));
+        assert!(!supports_ntt::<ModInt998244353>((1 << 23) + 1));
+        assert!(!supports_ntt::<ModInt1000000007>(4));
+        assert!(!supports_ntt::<StaticModInt<122_890>>(2));
+        assert!(supports_ntt::<StaticModInt<12289>>(1 << 12));
+    }
+}
@@ -0,0 +1,382 @@
+//! Floating-point fast Fourier transform and convolutions built on it.
+//!
+//! # Precision
+//!
+//! The roots of unity are computed directly with `sin`/`cos` rather than by
+//! repeated multiplication, so the rounding error of a transform of length
+//! `N` grows like `log2(N)` ulps. For [`convolution_f64`] the absolute error
+//! of each coefficient is roughly `max|a| * max|b| * N * log2(N) * 2^-53`;
+//! results rounded to integers are exact while
+//! `(sum a_i^2 + sum b_i^2) * log2(N) < 9 * 10^14`.
+//!
+//! [`convolution_mod_fft`] splits residues below `2^30` into 15-bit halves,
+//! so each of the four partial convolutions has `sum a_i^2 + sum b_i^2` at
+//! most `N * 2^30`. The bound above then guarantees exact results for
+//! transform lengths `N` up to `2^15`. Rounding errors of typical inputs
+//! partly cancel, so much longer products usually come out right as well,
+//! but that is not guaranteed.
+
+use crate::math::modint::ModIntLike;
+use std::cell::RefCell;
+use std::f64::consts::PI;
+use std::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+/// A complex number `re + im * i`.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Complex<T> {
+    pub re: T,
+    pub im: T,
+}
+
+impl<T> Complex<T> {
+    /// Creates `re + im * i`.
+    pub fn new(re: T, im: T) -> Self {
+        Self { re, im }
+    }
+}
+
+impl<T: Copy + Neg<Output = T>> Complex<T> {
+    /// Returns the complex conjugate.
+    pub fn conj(self) -> Self {
+        Self::new(self.re, -self.im)
+    }
+}
+
+impl<T: Copy + Mul<Output = T>> Complex<T> {
+    /// Multiplies both parts by the real `k`.
+    pub fn scale(self, k: T) -> Self {
+        Self::new(self.re * k, self.im * k)
+    }
+}
+
+impl Complex<f64> {
+    /// Returns `r * (cos(theta) + i * sin(theta))`.
+    pub fn from_polar(r: f64, theta: f64) -> Self {
+        Self::new(r * theta.cos(), r * theta.sin())
+    }
+
+    /// Returns the squared magnitude.
+    pub fn norm_sqr(self) -> f64 {
+        self.re * self.re + self.im * self.im
+    }
+
+    /// Returns the magnitude.
+    pub fn abs(self) -> f64 {
+        self.re.hypot(self.im)
+    }
+
+    /// Returns the argument in `(-pi, pi]`.
+    pub fn arg(self) -> f64 {
+        self.im.atan2(self.re)
+    }
+}
+
+impl<T: Add<Output = T>> Add for Complex<T> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self::new(self.re + rhs.re, self.im + rhs.im)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Complex<T> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self::new(self.re - rhs.re, self.im - rhs.im)
+    }
+}
+
+impl<T: Copy + Add<Output = T> + Sub<Output = T> + Mul<Output = T>> Mul for Complex<T> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        Self::new(
+            self.re * rhs.re - self.im * rhs.im,
+            self.re * rhs.im + self.im * rhs.re,
+        )
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Complex<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::new(-self.re, -self.im)
+    }
+}
+
+impl<T: Copy + Add<Output = T>> AddAssign for Complex<T> {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl<T: Copy + Sub<Output = T>> SubAssign for Complex<T> {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl<T: Copy + Add<Output = T> + Sub<Output = T> + Mul<Output = T>> MulAssign for Complex<T> {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+thread_local! {
+    /// `ROOTS[k + j] = exp(-i * pi * j / k)` for powers of two `k` and `j < k`.
+    static ROOTS: RefCell<Vec<Complex<f64>>> = RefCell::new(vec![Complex::new(1.0, 0.0); 2]);
+}
+
+/// Transforms `a` in place with `A_k = sum_j a_j * exp(-2 pi i jk / n)`.
+///
+/// With `inverse` set, the conjugate roots are used and the result is
+/// divided by `n`, so that the two directions are mutual inverses.
+///
+/// # Panics
+///
+/// Panics if `a.len()` is not a power of two.
+pub fn fft(a: &mut [Complex<f64>], inverse: bool) {
+    let n = a.len();
+    assert!(n.is_power_of_two(), "length must be a power of two");
+    if n == 1 {
+        return;
+    }
+
+    let mut j = 0;
+    for i in 1..n {
+        let mut bit = n >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j ^= bit;
+        if i < j {
+            a.swap(i, j);
+        }
+    }
+
+    ROOTS.with(|roots| {
+        let mut roots = roots.borrow_mut();
+        let mut k = roots.len() / 2;
+        while roots.len() < n {
+            k *= 2;
+            roots.extend((0..k).map(|j| Complex::from_polar(1.0, -PI * j as f64 / k as f64)));
+        }
+
+        let mut k = 1;
+        while k < n {
+            for block in a.chunks_exact_mut(2 * k) {
+                let (lo, hi) = block.split_at_mut(k);
+                for ((x, y), &w) in lo.iter_mut().zip(hi).zip(&roots[k..2 * k]) {
+                    let z = *y * if inverse { w.conj() } else { w };
+                    *y = *x - z;
+                    *x += z;
+                }
+            }
+            k *= 2;
+        }
+    });
+
+    if inverse {
+        let inv_n = 1.0 / n as f64;
+        for x in a.iter_mut() {
+            *x = x.scale(inv_n);
+        }
+    }
+}
+
+/// Returns the product of the real polynomials `a` and `b`.
+///
+/// See the [module documentation](self) for error bounds.
+///
+/// # Examples
+///
+/// ```
+/// use algorithm_rs::math::fft::convolution_f64;
+///
+/// let c = convolution_f64(&[1.0, 2.0], &[0.5, -1.0, 3.0]);
+/// let expected = [0.5, 0.0, 1.0, 6.0];
+/// assert!(c.iter().zip(&expected).all(|(x, y)| (x - y).abs() < 1e-9));
+/// ```
+pub fn convolution_f64(a: &[f64], b: &[f64]) -> Vec<f64> {
+    if a.is_empty() || b.is_empty() {
+        return Vec::new();
+    }
+
+    let len = a.len() + b.len() - 1;
+    let n = len.next_power_of_two();
+
+    // With p = a + ib, the imaginary part of p^2 is 2ab.
+    let mut p = vec![Complex::default(); n];
+    for (x, &v) in p.iter_mut().zip(a) {
+        x.re = v;
+    }
+    for (x, &v) in p.iter_mut().zip(b) {
+        x.im = v;
+    }
+
+    fft(&mut p, false);
+    for x in p.iter_mut() {
+        *x = *x * *x;
+    }
+    fft(&mut p, true);
+
+    p[..len].iter().map(|x| x.im / 2.0).collect()
+}
+
+/// Returns the product of `a` and `b` modulo any modulus, via four FFTs.
+///
+/// Residues are split as `hi * 2^15 + lo` and packed two to a complex
+/// number. See the [module documentation](self) for the precision limits.
+///
+/// # Panics
+///
+/// Panics if the modulus exceeds `2^30`.
+pub fn convolution_mod_fft<T: ModIntLike>(a: &[T], b: &[T]) -> Vec<T> {
+    if a.is_empty() || b.is_empty() {
+        return Vec::new();
+    }
+    assert!(
+        T::modulus() <= 1 << 30,
+        "modulus too large for 15-bit splitting"
+    );
+
+    const CUT: u32 = 1 << 15;
+    let len = a.len() + b.len() - 1;
+    let n = len.next_power_of_two();
+
+    let split = |values: &[T]| {
+        let mut packed = vec![Complex::default(); n];
+        for (x, v) in packed.iter_mut().zip(values) {
+            *x = Complex::new((v.val() / CUT) as f64, (v.val() % CUT) as f64);
+        }
+        fft(&mut packed, false);
+        packed
+    };
+    let l = split(a);
+    let r = split(b);
+
+    // Recover the transforms of the high and low halves of `a` from `l`.
+    let mut hi = vec![Complex::default(); n];
+    let mut lo = vec![Complex::default(); n];
+    for i in 0..n {
+        let j = (n - i) & (n - 1);
+        let a_hi = (l[i] + l[j].conj()).scale(0.5);
+        let a_lo = (l[i] - l[j].conj()) * Complex::new(0.0, -0.5);
+        hi[i] = a_hi * r[i];
+        lo[i] = a_lo * r[i];
+    }
+    fft(&mut hi, true);
+    fft(&mut lo, true);
+
+    let round = |x: f64| T::new(x.round() as i64);
+    let cut = T::from(CUT);
+    (0..len)
+        .map(|i| {
+            let hh = round(hi[i].re);
+            let mid = round(hi[i].im) + round(lo[i].re);
+            let ll = round(lo[i].im);
+            (hh * cut + mid) * cut + ll
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::modint::{ModInt1000000007, ModInt998244353, StaticModInt};
+    use crate::test_util::Rng;
+
+    fn naive<T: ModIntLike>(a: &[T], b: &[T]) -> Vec<T> {
+        let mut c = vec![T::zero(); a.len() + b.len() - 1];
+        for (i, &x) in a.iter().enumerate() {
+            for (j, &y) in b.iter().enumerate() {
+                c[i + j] += x * y;
+            }
+        }
+        c
+    }
+
+    #[test]
+    fn test_complex() {
+        let a = Complex::new(1.0, 2.0);
+        let b = Complex::new(3.0, -1.0);
+
+        assert_eq!(a * b, Complex::new(5.0, 5.0));
+        assert_eq!(a - b + b, a);
+        assert_eq!(a.conj(), Complex::new(1.0, -2.0));
+        assert_eq!(Complex::new(3.0, 4.0).abs(), 5.0);
+        assert!((Complex::from_polar(2.0, PI / 2.0) - Complex::new(0.0, 2.0)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_fft_roundtrip() {
+        let mut rng = Rng(5);
+        for h in 0..10 {
+            let a: Vec<Complex<f64>> = (0..1 << h)
+                .map(|_| Complex::new((rng.next() % 1000) as f64, (rng.next() % 1000) as f64))
+                .collect();
+            let mut b = a.clone();
+            fft(&mut b, false);
+            fft(&mut b, true);
+            assert!(a.iter().zip(&b).all(|(x, y)| (*x - *y).abs() < 1e-9));
+        }
+    }
+
+    #[test]
+    fn test_convolution_f64() {
+        let mut rng = Rng(6);
+        for &(n, m) in &[(1, 1), (3, 17), (100, 200), (513, 511)] {
+            let a: Vec<f64> = (0..n)
+                .map(|_| (rng.next() % 2001) as f64 - 1000.0)
+                .collect();
+            let b: Vec<f64> = (0..m)
+                .map(|_| (rng.next() % 2001) as f64 - 1000.0)
+                .collect();
+            let mut expected = vec![0.0; n + m - 1];
+            for (i, x) in a.iter().enumerate() {
+                for (j, y) in b.iter().enumerate() {
+                    expected[i + j] += x * y;
+                }
+            }
+
+            let c = convolution_f64(&a, &b);
+            assert_eq!(c.len(), expected.len());
+            assert!(c.iter().zip(&expected).all(|(x, y)| (x - y).abs() < 1e-3));
+        }
+    }
+
+    #[test]
+    fn test_convolution_mod_fft() {
+        let mut rng = Rng(7);
+        for &(n, m) in &[(1, 1), (10, 300), (1000, 999), (2048, 4096)] {
+            let a: Vec<ModInt1000000007> =
+                (0..n).map(|_| ModInt1000000007::new(rng.next())).collect();
+            let b: Vec<ModInt1000000007> =
+                (0..m).map(|_| ModInt1000000007::new(rng.next())).collect();
+            assert_eq!(convolution_mod_fft(&a, &b), naive(&a, &b));
+
+            let a: Vec<ModInt998244353> = a.iter().map(|x| ModInt998244353::new(x.val())).collect();
+            let b: Vec<ModInt998244353> = b.iter().map(|x| ModInt998244353::new(x.val())).collect();
+            assert_eq!(convolution_mod_fft(&a, &b), naive(&a, &b));
+        }
+
+        let a = [StaticModInt::<2>::new(1); 5];
+        assert_eq!(
+            convolution_mod_fft(&a, &a)
+                .iter()
+                .map(|x| x.val())
+                .collect::<Vec<_>>(),
+            vec![1, 0, 1, 0, 1, 0, 1, 0, 1]
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_convolution_mod_fft_large_modulus() {
+        let a = [StaticModInt::<4_294_967_291>::new(1); 2];
+        convolution_mod_fft(&a, &a);
+    }
+}
@@ -0,0 +1,87 @@
+//! The field abstraction shared by the generic linear algebra routines.
+
+use crate::math::modint::ModIntLike;
+use crate::num::{Integer, Rational};
+use std::fmt::Debug;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+/// A commutative ring in which every nonzero element is invertible.
+///
+/// Every [`ModIntLike`] type implements it; division by a non-unit panics,
+/// so generic algorithms over modular integers assume a prime modulus.
+/// [`Rational`] is exact as well. `f64` rounds, so elimination treats an
+/// entry as zero once it is below `1e-9` times the largest magnitude in its
+/// column, see [`is_negligible`](Field::is_negligible).
+pub trait Field:
+    Clone
+    + PartialEq
+    + Debug
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Neg<Output = Self>
+{
+    /// Returns the additive identity.
+    fn zero() -> Self;
+
+    /// Returns the multiplicative identity.
+    fn one() -> Self;
+
+    /// Returns `true` if `self` is the additive identity.
+    fn is_zero(&self) -> bool {
+        *self == Self::zero()
+    }
+
+    /// Returns `true` if `self` is zero up to rounding, relative to `scale`,
+    /// the largest entry of its column; exact fields ignore `scale`.
+    fn is_negligible(&self, _scale: &Self) -> bool {
+        self.is_zero()
+    }
+
+    /// Returns `true` if `self` makes a better pivot than the nonzero
+    /// `other`; exact fields take the first nonzero candidate.
+    fn is_better_pivot(&self, _other: &Self) -> bool {
+        false
+    }
+}
+
+impl<T: ModIntLike> Field for T {
+    fn zero() -> Self {
+        ModIntLike::zero()
+    }
+
+    fn one() -> Self {
+        ModIntLike::one()
+    }
+}
+
+impl<T: Integer> Field for Rational<T> {
+    fn zero() -> Self {
+        Rational::zero()
+    }
+
+    fn one() -> Self {
+        Rational::one()
+    }
+}
+
+impl Field for f64 {
+    fn zero() -> Self {
+        0.0
+    }
+
+    fn one() -> Self {
+        1.0
+    }
+
+    /// A relative tolerance does not depend on the units of the input.
+    fn is_negligible(&self, scale: &Self) -> bool {
+        self.abs() <= 1e-9 * scale.abs()
+    }
+
+    /// Partial pivoting: the largest magnitude keeps rounding errors small.
+    fn is_better_pivot(&self, other: &Self) -> bool {
+        self.abs() > other.abs()
+    }
+}
@@ -0,0 +1,405 @@
+//! Sums of `floor((a i + b) / m)` and blocks of equal `floor(n / i)`.
+//!
+//! [`floor_sum`] is the classic Euclid-like reduction. [`floor_sum_powers`]
+//! runs the universal Euclidean algorithm: the lattice path below the line
+//! `y = (a x + b) / m` is a word in the steps `U` and `R`, and folding it in
+//! a monoid of power sums takes `O(log m)` monoid products.
+
+use crate::math::modint::ModIntLike;
+
+/// Returns `sum_{0 <= i < n} floor((a i + b) / m)`.
+///
+/// `a` and `b` may be negative. Intermediate values stay below about
+/// `n (|a| n + |b|) / m`, so any input with `n`, `|a|` and `|b|` below `2^40`
+/// is safe.
+///
+/// # Panics
+///
+/// Panics if `n` is negative or `m` is not positive.
+///
+/// # Examples
+///
+/// ```
+/// use algorithm_rs::math::floor_sum;
+///
+/// assert_eq!(floor_sum(4, 10, 6, 3), 0 + 0 + 1 + 2);
+/// assert_eq!(floor_sum(3, 2, -3, 1), 0 + -1 + -3);
+/// ```
+pub fn floor_sum(n: i64, m: i64, a: i64, b: i64) -> i128 {
+    assert!(n >= 0, "n must be non-negative");
+    assert!(m > 0, "modulus must be positive");
+    let (n, m, a, b) = (n as i128, m as i128, a as i128, b as i128);
+
+    // Split off the negative parts: a = qa m + ra and b = qb m + rb.
+    let (qa, ra) = (a.div_euclid(m), a.rem_euclid(m));
+    let (qb, rb) = (b.div_euclid(m), b.rem_euclid(m));
+    let rest = floor_sum_unsigned(n as u128, m as u128, ra as u128, rb as u128);
+    qa * (n * (n - 1) / 2) + qb * n + rest as i128
+}
+
+fn floor_sum_unsigned(mut n: u128, mut m: u128, mut a: u128, mut b: u128) -> u128 {
+    let mut result = 0;
+    loop {
+        if a >= m {
+            result += n * n.saturating_sub(1) / 2 * (a / m);
+            a %= m;
+        }
+        if b >= m {
+            result += n * (b / m);
+            b %= m;
+        }
+
+        // Count the lattice points by the transposed problem.
+        let y_max = a * n + b;
+        if y_max < m {
+            return result;
+        }
+        n = y_max / m;
+        b = y_max % m;
+        (m, a) = (a, m);
+    }
+}
+
+/// Returns `sums[p][q] = sum_{0 <= i < n} i^p floor((a i + b) / m)^q` for
+/// all `p <= k1` and `q <= k2`.
+///
+/// `a` and `b` may be negative. The cost is `O((k1 + k2)^2 k2^2 log m)`.
+///
+/// # Panics
+///
+/// Panics if `n` is negative or `m` is not positive.
+///
+/// # Examples
+///
+/// ```
+/// use algorithm_rs::math::floor_sum::floor_sum_powers;
+/// use algorithm_rs::math::modint::ModInt998244353 as Mint;
+///
+/// // floor(i / 2) for i < 5 is 0, 0, 1, 1, 2.
+/// let sums = floor_sum_powers::<Mint>(5, 2, 1, 0, 1, 2);
+/// assert_eq!(sums[0][2], Mint::new(1 + 1 + 4));
+/// assert_eq!(sums[1][1], Mint::new(2 + 3 + 8));
+/// ```
+pub fn floor_sum_powers<T: ModIntLike>(
+    n: i64,
+    m: i64,
+    a: i64,
+    b: i64,
+    k1: usize,
+    k2: usize,
+) -> Vec<Vec<T>> {
+    assert!(n >= 0, "n must be non-negative");
+    assert!(m > 0, "modulus must be positive");
+
+    // floor((a i + b) / m) = qa i + qb + floor((ra i + rb) / m), where the last
+    // term comes from the path, and the rest is expanded binomially.
+    let (qa, ra) = (a.div_euclid(m), a.rem_euclid(m));
+    let (qb, rb) = (b.div_euclid(m), b.rem_euclid(m));
+    let kx = k1 + k2;
+    let binom = pascal::<T>(kx);
+
+    // Steps R advance i = 1, 2, ..., n - 1; i = 0 contributes only 0^0 0^0.
+    let up = Node::up(kx, k2);
+    let right = Node::right(kx, k2);
+    let path = euclid(
+        ra as u128,
+        m as u128,
+        rb as u128,
+        (n as u128).saturating_sub(1),
+        up,
+        right,
+        &binom,
+    );
+    let mut base = path.sums;
+    if n > 0 {
+        base[0][0] += T::one();
+    }
+
+    let (qa, qb) = (T::from(qa), T::from(qb));
+    let mut sums = vec![vec![T::zero(); k2 + 1]; k1 + 1];
+    for (p, row) in sums.iter_mut().enumerate() {
+        for (q, sum) in row.iter_mut().enumerate() {
+            // (qa i + qb + f)^q = sum_{s + t + u = q} q! / (s! t! u!) (qa i)^s qb^t f^u
+            for s in 0..=q {
+                for t in 0..=q - s {
+                    let u = q - s - t;
+                    let coef = binom[q][s] * binom[q - s][t] * qa.pow(s as u64) * qb.pow(t as u64);
+                    *sum += coef * base[p + s][u];
+                }
+            }
+        }
+    }
+
+    sums
+}
+
+/// Returns Pascal's triangle up to row `n`.
+fn pascal<T: ModIntLike>(n: usize) -> Vec<Vec<T>> {
+    let mut c = vec![vec![T::zero(); n + 1]; n + 1];
+    for i in 0..=n {
+        c[i][0] = T::one();
+        for j in 1..=i {
+            c[i][j] = c[i - 1][j - 1] + c[i - 1][j];
+        }
+    }
+
+    c
+}
+
+/// A word in `U` and `R`: `x` steps `R`, `y` steps `U`, and for each `R` the
+/// value `x^p y^q` at that point summed into `sums[p][q]`.
+#[derive(Clone, Debug)]
+struct Node<T> {
+    x: T,
+    y: T,
+    sums: Vec<Vec<T>>,
+}
+
+impl<T: ModIntLike> Node<T> {
+    fn identity(kx: usize, ky: usize) -> Self {
+        Self {
+            x: T::zero(),
+            y: T::zero(),
+            sums: vec![vec![T::zero(); ky + 1]; kx + 1],
+        }
+    }
+
+    fn up(kx: usize, ky: usize) -> Self {
+        Self {
+            y: T::one(),
+            ..Self::identity(kx, ky)
+        }
+    }
+
+    fn right(kx: usize, ky: usize) -> Self {
+        let mut node = Self {
+            x: T::one(),
+            ..Self::identity(kx, ky)
+        };
+        for row in node.sums.iter_mut() {
+            row[0] = T::one();
+        }
+        node
+    }
+
+    /// Concatenates two words; the points of `other` are shifted by `self.x`
+    /// and `self.y`.
+    fn mul(&self, other: &Self, binom: &[Vec<T>]) -> Self {
+        let (kx, ky) = (self.sums.len() - 1, self.sums[0].len() - 1);
+        let xs: Vec<T> = (0..=kx).map(|i| self.x.pow(i as u64)).collect();
+        let ys: Vec<T> = (0..=ky).map(|j| self.y.pow(j as u64)).collect();
+
+        let mut sums = self.sums.clone();
+        for (p, row) in sums.iter_mut().enumerate() {
+            for (q, sum) in row.iter_mut().enumerate() {
+                // (x + x')^p (y + y')^q expanded over the points of other.
+                for i in 0..=p {
+                    let cx = binom[p][i] * xs[p - i];
+                    for j in 0..=q {
+                        *sum += cx * binom[q][j] * ys[q - j] * other.sums[i][j];
+                    }
+                }
+            }
+        }
+
+        Self {
+            x: self.x + other.x,
+            y: self.y + other.y,
+            sums,
+        }
+    }
+
+    fn pow(&self, mut exp: u128, binom: &[Vec<T>]) -> Self {
+        let (kx, ky) = (self.sums.len() - 1, self.sums[0].len() - 1);
+        let mut result = Self::identity(kx, ky);
+        let mut base = self.clone();
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result = result.mul(&base, binom);
+            }
+            base = base.mul(&base, binom);
+            exp >>= 1;
+        }
+        result
+    }
+}
+
+/// Folds the word of `y = floor((p x + r) / q)` for `x = 1..=l`: before the
+/// `x`-th `R` come as many `U` as `y` has grown. Requires `r < q`.
+fn euclid<T: ModIntLike>(
+    p: u128,
+    q: u128,
+    r: u128,
+    l: u128,
+    up: Node<T>,
+    right: Node<T>,
+    binom: &[Vec<T>],
+) -> Node<T> {
+    let (kx, ky) = (up.sums.len() - 1, up.sums[0].len() - 1);
+    if l == 0 {
+        return Node::identity(kx, ky);
+    }
+    if p >= q {
+        let right = up.pow(p / q, binom).mul(&right, binom);
+        return euclid(p % q, q, r, l, up, right, binom);
+    }
+    let m = (p * l + r) / q;
+    if m == 0 {
+        return right.pow(l, binom);
+    }
+
+    // Swap the roles of U and R, reflecting the line about y = x.
+    let cnt = l - (q * m - r - 1) / p;
+    let head = right.pow((q - r - 1) / p, binom).mul(&up, binom);
+    let tail = right.pow(cnt, binom);
+    let middle = euclid(q, p, (q - r - 1) % p, m - 1, right, up, binom);
+    head.mul(&middle, binom).mul(&tail, binom)
+}
+
+/// Iterator over the maximal blocks `lo..=hi` of `1..=n` on which
+/// `floor(n / i)` is constant, yielding `(lo, hi, floor(n / lo))`.
+///
+/// There are at most `2 sqrt(n)` blocks.
+///
+/// # Examples
+///
+/// ```
+/// use algorithm_rs::math::floor_sum::FloorBlocks;
+///
+/// let blocks: Vec<_> = FloorBlocks::new(10).collect();
+/// assert_eq!(blocks, vec![(1, 1, 10), (2, 2, 5), (3, 3, 3), (4, 5, 2), (6, 10, 1)]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct FloorBlocks {
+    n: u64,
+    next: u64,
+}
+
+impl FloorBlocks {
+    /// Prepares to enumerate the blocks of `1..=n`.
+    pub fn new(n: u64) -> Self {
+        Self { n, next: 1 }
+    }
+}
+
+impl Iterator for FloorBlocks {
+    type Item = (u64, u64, u64);
+
+    fn next(&mut self) -> Option<(u64, u64, u64)> {
+        // next wraps to zero after the block ending at u64::MAX.
+        if self.next == 0 || self.next > self.n {
+            return None;
+        }
+        let lo = self.next;
+        let q = self.n / lo;
+        let hi = self.n / q;
+        self.next = hi.wrapping_add(1);
+        Some((lo, hi, q))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::modint::ModInt998244353;
+    use crate::test_util::Rng;
+
+    type Mint = ModInt998244353;
+
+    fn naive(n: i64, m: i64, a: i64, b: i64) -> i128 {
+        (0..n).map(|i| (a * i + b).div_euclid(m) as i128).sum()
+    }
+
+    #[test]
+    fn test_floor_sum() {
+        for n in 0..20 {
+            for m in 1..20 {
+                for a in -20..20 {
+                    for b in -20..20 {
+                        assert_eq!(floor_sum(n, m, a, b), naive(n, m, a, b));
+                    }
+                }
+            }
+        }
+
+        let mut rng = Rng(80);
+        for _ in 0..100 {
+            let n = (rng.next() % 1000) as i64;
+            let m = (rng.next() % 1_000_000_000 + 1) as i64;
+            let a = (rng.next() % 2_000_000_000) as i64 - 1_000_000_000;
+            let b = (rng.next() % 2_000_000_000) as i64 - 1_000_000_000;
+            assert_eq!(floor_sum(n, m, a, b), naive(n, m, a, b));
+        }
+
+        // n (n - 1) / 2 alone is far beyond i64.
+        let n = 1 << 39;
+        assert_eq!(floor_sum(n, 1, 1, 0), (n as i128) * (n as i128 - 1) / 2);
+    }
+
+    #[test]
+    fn test_floor_sum_powers() {
+        let mut rng = Rng(81);
+        for _ in 0..300 {
+            let n = (rng.next() % 60) as i64;
+            let m = (rng.next() % 30 + 1) as i64;
+            let a = (rng.next() % 100) as i64 - 50;
+            let b = (rng.next() % 100) as i64 - 50;
+            let (k1, k2) = ((rng.next() % 4) as usize, (rng.next() % 4) as usize);
+            let sums = floor_sum_powers::<Mint>(n, m, a, b, k1, k2);
+            for (p, row) in sums.iter().enumerate() {
+                for (q, &sum) in row.iter().enumerate() {
+                    let expected: Mint = (0..n)
+                        .map(|i| {
+                            let y = Mint::new((a * i + b).div_euclid(m));
+                            Mint::new(i).pow(p as u64) * y.pow(q as u64)
+                        })
+                        .sum();
+                    assert_eq!(
+                        sum, expected,
+                        "n={} m={} a={} b={} p={} q={}",
+                        n, m, a, b, p, q
+                    );
+                }
+            }
+        }
+
+        // The degree-zero entry agrees with floor_sum on large inputs.
+        for _ in 0..100 {
+            let n = (rng.next() % (1 << 30)) as i64;
+            let m = (rng.next() % (1 << 30) + 1) as i64;
+            let a = (rng.next() % (1 << 31)) as i64 - (1 << 30);
+            let b = (rng.next() % (1 << 31)) as i64 - (1 << 30);
+            let sums = floor_sum_powers::<Mint>(n, m, a, b, 0, 1);
+            assert_eq!(sums[0][0], Mint::new(n));
+            assert_eq!(sums[0][1], Mint::new(floor_sum(n, m, a, b)));
+        }
+    }
+
+    #[test]
+    fn test_floor_blocks() {
+        for n in 0..500u64 {
+            let mut expected_lo = 1;
+            let mut count = 0;
+            for (lo, hi, q) in FloorBlocks::new(n) {
+                assert_eq!(lo, expected_lo);
+                assert!(lo <= hi && q > 0);
+                assert!((lo..=hi).all(|i| n / i == q));
+                expected_lo = hi + 1;
+                count += 1;
+            }
+            assert_eq!(expected_lo, n + 1);
+            assert!(count <= 2 * n.isqrt());
+        }
+
+        let blocks: Vec<_> = FloorBlocks::new(u64::MAX).skip(1).take(1).collect();
+        assert_eq!(blocks, vec![(2, 2, u64::MAX / 2)]);
+        let tail = FloorBlocks {
+            n: u64::MAX,
+            next: u64::MAX / 2 + 1,
+        };
+        assert_eq!(
+            tail.collect::<Vec<_>>(),
+            vec![(u64::MAX / 2 + 1, u64::MAX, 1)]
+        );
+    }
+}
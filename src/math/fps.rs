@@ -0,0 +1,520 @@
+//! Formal power series over modular integers.
+//!
+//! Products use [`convolution`], so every operation works for any modulus;
+//! the Newton iterations run in `O(n log n)` when the modulus is
+//! NTT-friendly. Operations that divide by small integers (`log`, `exp`,
+//! `pow`, `sqrt`, `integral`) additionally assume the modulus is a prime
+//! larger than the number of requested terms.
+
+use crate::math::convolution::{convolution, intt, ntt, supports_ntt};
+use crate::math::modint::ModIntLike;
+use std::ops::{Add, AddAssign, Deref, DerefMut, Mul, MulAssign, Neg, Sub, SubAssign};
+
+/// A power series `a[0] + a[1] x + a[2] x^2 + ...` stored by its coefficients.
+///
+/// The series dereferences to its coefficient slice. Arithmetic keeps every
+/// coefficient (a product of lengths `n` and `m` has `n + m - 1` terms); the
+/// analytic operations take the number of terms to compute.
+///
+/// # Examples
+///
+/// ```
+/// use algorithm_rs::math::fps::FormalPowerSeries;
+/// use algorithm_rs::math::modint::ModInt998244353 as Mint;
+///
+/// // 1 / (1 - x - x^2) generates the Fibonacci numbers.
+/// let f = FormalPowerSeries::from(vec![Mint::new(1), -Mint::new(1), -Mint::new(1)]);
+/// let fib: Vec<u32> = f.inv(8).iter().map(|x| x.val()).collect();
+/// assert_eq!(fib, vec![1, 1, 2, 3, 5, 8, 13, 21]);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct FormalPowerSeries<T> {
+    coef: Vec<T>,
+}
+
+impl<T: ModIntLike> FormalPowerSeries<T> {
+    /// Creates the series with coefficients `coef`.
+    pub fn new(coef: Vec<T>) -> Self {
+        Self { coef }
+    }
+
+    /// Creates `n` zero coefficients.
+    pub fn zeros(n: usize) -> Self {
+        Self::new(vec![T::zero(); n])
+    }
+
+    /// Returns the coefficients.
+    pub fn into_vec(self) -> Vec<T> {
+        self.coef
+    }
+
+    /// Returns the coefficient of `x^i`, which is zero past the end.
+    pub fn coeff(&self, i: usize) -> T {
+        self.coef.get(i).copied().unwrap_or_else(T::zero)
+    }
+
+    /// Removes trailing zero coefficients.
+    pub fn shrink(&mut self) {
+        while self.coef.last() == Some(&T::zero()) {
+            self.coef.pop();
+        }
+    }
+
+    /// Returns the first `n` coefficients, padding with zeros.
+    pub fn truncate(&self, n: usize) -> Self {
+        let mut coef = self.coef[..n.min(self.len())].to_vec();
+        coef.resize(n, T::zero());
+        Self::new(coef)
+    }
+
+    /// Multiplies by `x^k`; a negative `k` divides and drops the low terms.
+    pub fn shift(&self, k: isize) -> Self {
+        if k >= 0 {
+            let mut coef = vec![T::zero(); k as usize];
+            coef.extend_from_slice(&self.coef);
+            Self::new(coef)
+        } else {
+            Self::new(self.coef[k.unsigned_abs().min(self.len())..].to_vec())
+        }
+    }
+
+    /// Multiplies every coefficient by `k`.
+    pub fn scale(&self, k: T) -> Self {
+        self.coef.iter().map(|&x| x * k).collect()
+    }
+
+    /// Returns the formal derivative.
+    pub fn derivative(&self) -> Self {
+        self.coef
+            .iter()
+            .enumerate()
+            .skip(1)
+            .map(|(i, &x)| x * T::from(i))
+            .collect()
+    }
+
+    /// Returns the antiderivative with zero constant term.
+    pub fn integral(&self) -> Self {
+        let inv = inverses::<T>(self.len() + 1);
+        let mut coef = Vec::with_capacity(self.len() + 1);
+        coef.push(T::zero());
+        coef.extend(self.coef.iter().zip(&inv[1..]).map(|(&x, &y)| x * y));
+        Self::new(coef)
+    }
+
+    /// Returns the first `n` coefficients of `1 / self`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the constant term is not invertible.
+    pub fn inv(&self, n: usize) -> Self {
+        let c = self
+            .coeff(0)
+            .checked_inv()
+            .expect("constant term must be invertible");
+        let mut g = vec![c];
+        let mut m = 1;
+        while m < n {
+            if supports_ntt::<T>(2 * m) {
+                self.inv_step_ntt(&mut g, m);
+            } else {
+                // g <- g (2 - f g)
+                let fg = convolution(&self.truncate(2 * m), &g);
+                let e: Vec<T> = fg[m..2 * m].iter().map(|&x| -x).collect();
+                let h = convolution(&e, &g);
+                g.extend_from_slice(&h[..m]);
+            }
+            m *= 2;
+        }
+
+        g.truncate(n);
+        Self::new(g)
+    }
+
+    /// Doubles the `m` known terms of `g` with two cyclic products of length `2m`.
+    fn inv_step_ntt(&self, g: &mut Vec<T>, m: usize) {
+        let mut f = self.truncate(2 * m).into_vec();
+        let mut g_hat = g.clone();
+        g_hat.resize(2 * m, T::zero());
+        ntt(&mut f);
+        ntt(&mut g_hat);
+        for (x, &y) in f.iter_mut().zip(&g_hat) {
+            *x *= y;
+        }
+        intt(&mut f);
+
+        // f g is 1 below x^m, and the wrap-around only touches that half.
+        f[..m].fill(T::zero());
+        ntt(&mut f);
+        for (x, &y) in f.iter_mut().zip(&g_hat) {
+            *x *= y;
+        }
+        intt(&mut f);
+        g.extend(f[m..].iter().map(|&x| -x));
+    }
+
+    /// Returns the first `n` coefficients of `log(self)`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the constant term is not one.
+    pub fn log(&self, n: usize) -> Self {
+        assert!(self.coeff(0) == T::one(), "constant term must be one");
+        if n == 0 {
+            return Self::default();
+        }
+
+        let f = self.truncate(n);
+        let d = &f.derivative() * &f.inv(n - 1);
+        d.truncate(n - 1).integral()
+    }
+
+    /// Returns the first `n` coefficients of `exp(self)`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the constant term is not zero.
+    pub fn exp(&self, n: usize) -> Self {
+        assert!(self.coeff(0) == T::zero(), "constant term must be zero");
+
+        // g <- g (1 - log g + f)
+        let mut g = Self::new(vec![T::one()]);
+        let mut m = 1;
+        while m < n {
+            m *= 2;
+            let mut h = self.truncate(m) - g.log(m);
+            h[0] += T::one();
+            g = (&g * &h).truncate(m);
+        }
+
+        g.truncate(n)
+    }
+
+    /// Returns the first `n` coefficients of `self^k`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use algorithm_rs::math::fps::FormalPowerSeries;
+    /// use algorithm_rs::math::modint::ModInt998244353 as Mint;
+    ///
+    /// let f = FormalPowerSeries::from(vec![Mint::new(0), Mint::new(1), Mint::new(1)]);
+    /// let g: Vec<u32> = f.pow(3, 6).iter().map(|x| x.val()).collect();
+    /// assert_eq!(g, vec![0, 0, 0, 1, 3, 3]);
+    /// ```
+    pub fn pow(&self, k: u64, n: usize) -> Self {
+        if k == 0 {
+            return Self::new(vec![T::one()]).truncate(n);
+        }
+        let i = match self.coef.iter().position(|&x| x != T::zero()) {
+            Some(i) => i,
+            None => return Self::zeros(n),
+        };
+        let low = match (i as u64).checked_mul(k) {
+            Some(low) if low < n as u64 => low as usize,
+            _ => return Self::zeros(n),
+        };
+
+        let c = self.coef[i];
+        let f = self.shift(-(i as isize)).scale(c.inv());
+        let g = f.log(n - low).scale(T::new(k)).exp(n - low);
+        g.scale(c.pow(k)).shift(low as isize)
+    }
+
+    /// Returns the first `n` coefficients of a square root of `self`, if any.
+    ///
+    /// Among the two roots, the one whose lowest coefficient is the smaller
+    /// residue is returned.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the modulus is not an odd prime.
+    pub fn sqrt(&self, n: usize) -> Option<Self> {
+        let i = match self.coef.iter().position(|&x| x != T::zero()) {
+            Some(i) => i,
+            None => return Some(Self::zeros(n)),
+        };
+        if i % 2 == 1 {
+            return None;
+        }
+        if i / 2 >= n {
+            return Some(Self::zeros(n));
+        }
+
+        let f = self.shift(-(i as isize));
+        let m = n - i / 2;
+        let inv2 = T::new(2).inv();
+        let mut g = Self::new(vec![sqrt_mod(f[0])?]);
+        let mut k = 1;
+        while k < m {
+            k *= 2;
+            // g <- (g + f / g) / 2
+            g = (&g + &(&f.truncate(k) * &g.inv(k))).truncate(k).scale(inv2);
+        }
+
+        Some(g.truncate(m).shift((i / 2) as isize))
+    }
+}
+
+/// Returns `[0, 1, 1/2, ..., 1/(n-1)]` (the first entry is unused).
+fn inverses<T: ModIntLike>(n: usize) -> Vec<T> {
+    let m = T::modulus() as usize;
+    let mut inv = vec![T::zero(); n.max(2)];
+    inv[1] = T::one();
+    for i in 2..n {
+        inv[i] = -inv[m % i] * T::from(m / i);
+    }
+
+    inv
+}
+
+/// Returns the square root of `a` with the smaller residue, modulo an odd prime.
+fn sqrt_mod<T: ModIntLike>(a: T) -> Option<T> {
+    let p = T::modulus();
+    if a == T::zero() {
+        return Some(a);
+    }
+    if a.pow(((p - 1) / 2) as u64) != T::one() {
+        return None;
+    }
+
+    // Tonelli-Shanks with p - 1 = q 2^s.
+    let s = (p - 1).trailing_zeros();
+    let q = (p - 1) >> s;
+    let z = (2..p)
+        .map(T::from)
+        .find(|z| z.pow(((p - 1) / 2) as u64) != T::one())
+        .unwrap();
+    let mut c = z.pow(q as u64);
+    let mut x = a.pow(q.div_ceil(2) as u64);
+    let mut t = a.pow(q as u64);
+    let mut m = s;
+    while t != T::one() {
+        let mut i = 0;
+        let mut t2 = t;
+        while t2 != T::one() {
+            t2 *= t2;
+            i += 1;
+        }
+        let b = c.pow(1 << (m - i - 1));
+        x *= b;
+        c = b * b;
+        t *= c;
+        m = i;
+    }
+
+    Some(if x.val() <= p - x.val() { x } else { -x })
+}
+
+impl<T> Deref for FormalPowerSeries<T> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        &self.coef
+    }
+}
+
+impl<T> DerefMut for FormalPowerSeries<T> {
+    fn deref_mut(&mut self) -> &mut [T] {
+        &mut self.coef
+    }
+}
+
+impl<T: ModIntLike> From<Vec<T>> for FormalPowerSeries<T> {
+    fn from(coef: Vec<T>) -> Self {
+        Self::new(coef)
+    }
+}
+
+impl<T: ModIntLike> FromIterator<T> for FormalPowerSeries<T> {
+    fn from_iter<It: IntoIterator<Item = T>>(iter: It) -> Self {
+        Self::new(iter.into_iter().collect())
+    }
+}
+
+impl<T: ModIntLike> Neg for FormalPowerSeries<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        self.coef.into_iter().map(|x| -x).collect()
+    }
+}
+
+impl<T: ModIntLike> AddAssign<&Self> for FormalPowerSeries<T> {
+    fn add_assign(&mut self, rhs: &Self) {
+        if self.len() < rhs.len() {
+            self.coef.resize(rhs.len(), T::zero());
+        }
+        for (x, &y) in self.coef.iter_mut().zip(&rhs.coef) {
+            *x += y;
+        }
+    }
+}
+
+impl<T: ModIntLike> SubAssign<&Self> for FormalPowerSeries<T> {
+    fn sub_assign(&mut self, rhs: &Self) {
+        if self.len() < rhs.len() {
+            self.coef.resize(rhs.len(), T::zero());
+        }
+        for (x, &y) in self.coef.iter_mut().zip(&rhs.coef) {
+            *x -= y;
+        }
+    }
+}
+
+impl<T: ModIntLike> MulAssign<&Self> for FormalPowerSeries<T> {
+    fn mul_assign(&mut self, rhs: &Self) {
+        self.coef = convolution(&self.coef, &rhs.coef);
+    }
+}
+
+macro_rules! impl_fps_ops {
+    ($($trait:ident $method:ident $assign:ident $assign_method:ident),*) => {
+        $(
+            impl<T: ModIntLike> $assign for FormalPowerSeries<T> {
+                fn $assign_method(&mut self, rhs: Self) {
+                    *self = std::mem::take(self).$method(&rhs);
+                }
+            }
+
+            impl<T: ModIntLike> $trait<&Self> for FormalPowerSeries<T> {
+                type Output = Self;
+
+                fn $method(mut self, rhs: &Self) -> Self {
+                    $assign::$assign_method(&mut self, rhs);
+                    self
+                }
+            }
+
+            impl<T: ModIntLike> $trait for FormalPowerSeries<T> {
+                type Output = Self;
+
+                fn $method(self, rhs: Self) -> Self {
+                    self.$method(&rhs)
+                }
+            }
+
+            impl<T: ModIntLike> $trait for &FormalPowerSeries<T> {
+                type Output = FormalPowerSeries<T>;
+
+                fn $method(self, rhs: Self) -> FormalPowerSeries<T> {
+                    self.clone().$method(rhs)
+                }
+            }
+        )*
+    };
+}
+
+impl_fps_ops!(
+    Add add AddAssign add_assign,
+    Sub sub SubAssign sub_assign,
+    Mul mul MulAssign mul_assign
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::modint::{ModInt1000000007, ModInt998244353, StaticModInt};
+    use crate::test_util::Rng;
+
+    type Fps = FormalPowerSeries<ModInt998244353>;
+
+    fn random<T: ModIntLike>(rng: &mut Rng, n: usize) -> FormalPowerSeries<T> {
+        (0..n).map(|_| T::new(rng.next())).collect()
+    }
+
+    fn vals<T: ModIntLike>(f: &FormalPowerSeries<T>) -> Vec<u32> {
+        f.iter().map(|x| x.val()).collect()
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let f: Fps = [1, 2, 3].iter().map(|&x| ModInt998244353::new(x)).collect();
+        let g: Fps = [5, 7].iter().map(|&x| ModInt998244353::new(x)).collect();
+
+        assert_eq!(vals(&(&f + &g)), vec![6, 9, 3]);
+        assert_eq!(vals(&(&g - &f)), vec![4, 5, 998244350]);
+        assert_eq!(vals(&(&f * &g)), vec![5, 17, 29, 21]);
+        assert_eq!(vals(&f.derivative()), vec![2, 6]);
+        assert_eq!(f.derivative().integral().shift(-1), f.shift(-1));
+        assert_eq!(vals(&f.shift(2)), vec![0, 0, 1, 2, 3]);
+        assert_eq!(vals(&f.truncate(5)), vec![1, 2, 3, 0, 0]);
+    }
+
+    #[test]
+    fn test_inv() {
+        let mut rng = Rng(10);
+        for n in [1, 2, 7, 64, 300, 1000] {
+            let mut f: Fps = random(&mut rng, n);
+            f[0] = ModInt998244353::new(3);
+            let g = f.inv(n);
+            assert_eq!((&f * &g).truncate(n), Fps::new(vec![1.into()]).truncate(n));
+
+            let f: FormalPowerSeries<ModInt1000000007> = f.iter().map(|x| x.val().into()).collect();
+            let g = f.inv(n);
+            let one = FormalPowerSeries::new(vec![1.into()]).truncate(n);
+            assert_eq!((&f * &g).truncate(n), one);
+        }
+    }
+
+    #[test]
+    fn test_log_exp() {
+        let mut rng = Rng(11);
+        for n in [1, 5, 100, 513] {
+            let mut f: Fps = random(&mut rng, n);
+            f[0] = ModInt998244353::zero();
+            let g = f.exp(n);
+            assert_eq!(g.log(n), f);
+
+            // exp(x) = sum x^k / k!
+            let x = Fps::new(vec![0.into(), 1.into()]);
+            let e = x.exp(n);
+            let mut fact = ModInt998244353::one();
+            for (k, &c) in e.iter().enumerate() {
+                assert_eq!(c * fact, ModInt998244353::one());
+                fact *= ModInt998244353::from(k + 1);
+            }
+        }
+    }
+
+    #[test]
+    fn test_pow() {
+        let mut rng = Rng(12);
+        for n in [1, 6, 50, 200] {
+            let f: Fps = random(&mut rng, 4).shift(2);
+            for k in [0, 1, 2, 5, 40, 100] {
+                let mut expected = Fps::new(vec![1.into()]);
+                for _ in 0..k {
+                    expected = (&expected * &f).truncate(n);
+                }
+                assert_eq!(f.pow(k, n), expected.truncate(n));
+            }
+        }
+        assert_eq!(
+            Fps::zeros(3).pow(0, 3),
+            Fps::new(vec![1.into(), 0.into(), 0.into()])
+        );
+    }
+
+    #[test]
+    fn test_sqrt() {
+        let mut rng = Rng(13);
+        for n in [1, 4, 100, 600] {
+            let g: Fps = random(&mut rng, n).shift(4);
+            let f = (&g * &g).truncate(n + 4);
+            let h = f.sqrt(n + 4).unwrap();
+            assert_eq!((&h * &h).truncate(n + 4), f);
+        }
+
+        let f = Fps::new(vec![0.into(), 1.into()]);
+        assert_eq!(f.sqrt(3), None);
+
+        // 3 is a quadratic non-residue modulo 7.
+        let f = FormalPowerSeries::new(vec![StaticModInt::<7>::new(3)]);
+        assert_eq!(f.sqrt(2), None);
+        let f = FormalPowerSeries::new(vec![StaticModInt::<7>::new(2), 1.into()]);
+        let h = f.sqrt(5).unwrap();
+        assert_eq!(h[0].val(), 3);
+        assert_eq!((&h * &h).truncate(5), f.truncate(5));
+    }
+}
@@ -0,0 +1,250 @@
+//! Linear recurrences: recovering them from a prefix and jumping far ahead.
+//!
+//! A recurrence is given by its coefficients `c = [c_1, ..., c_d]` with
+//! `s[i] = c_1 s[i-1] + ... + c_d s[i-d]` for `i >= d`.
+
+use crate::math::convolution::{convolution, supports_ntt};
+use crate::math::field::Field;
+use crate::math::modint::ModIntLike;
+
+/// Returns the shortest recurrence satisfied by all of `s`.
+///
+/// With `2d` terms of a sequence of order `d` the result is exact.
+///
+/// # Examples
+///
+/// ```
+/// use algorithm_rs::math::linear_recurrence::berlekamp_massey;
+/// use algorithm_rs::math::modint::ModInt998244353 as Mint;
+///
+/// let s: Vec<Mint> = [0, 1, 1, 2, 3, 5, 8].iter().map(|&x| Mint::new(x)).collect();
+/// assert_eq!(berlekamp_massey(&s), vec![Mint::new(1), Mint::new(1)]);
+/// ```
+pub fn berlekamp_massey<T: Field>(s: &[T]) -> Vec<T> {
+    let mut c: Vec<T> = Vec::new();
+    let mut b: Vec<T> = Vec::new();
+    let mut len = 0;
+    let mut gap = 1;
+    let mut last = T::one();
+
+    for i in 0..s.len() {
+        let mut d = s[i].clone();
+        for (j, cj) in c.iter().enumerate() {
+            d = d - cj.clone() * s[i - 1 - j].clone();
+        }
+        if d.is_zero() {
+            gap += 1;
+            continue;
+        }
+
+        // C(x) <- C(x) - d / last * x^gap * B(x) on the polynomials 1 - sum c_j x^j.
+        let coef = d.clone() / last.clone();
+        let prev = c.clone();
+        if c.len() < gap + b.len() {
+            c.resize(gap + b.len(), T::zero());
+        }
+        c[gap - 1] = c[gap - 1].clone() + coef.clone();
+        for (j, bj) in b.iter().enumerate() {
+            c[gap + j] = c[gap + j].clone() - coef.clone() * bj.clone();
+        }
+
+        if 2 * len <= i {
+            len = i + 1 - len;
+            b = prev;
+            last = d;
+            gap = 1;
+        } else {
+            gap += 1;
+        }
+    }
+
+    c.resize(len, T::zero());
+    c
+}
+
+/// Returns `[x^k] p(x) / q(x)` by Bostan–Mori in `O(d log d log k)`.
+///
+/// # Panics
+///
+/// Panics if `q[0]` is not invertible.
+pub fn bostan_mori<T: ModIntLike>(p: &[T], q: &[T], mut k: u64) -> T {
+    let mut p = p.to_vec();
+    let mut q = q.to_vec();
+    while k > 0 && !p.is_empty() {
+        // p(x) / q(x) = p(x) q(-x) / (q(x) q(-x)), whose denominator is even.
+        let q_neg: Vec<T> = q
+            .iter()
+            .enumerate()
+            .map(|(i, &x)| if i % 2 == 0 { x } else { -x })
+            .collect();
+        let u = convolution(&p, &q_neg);
+        let v = convolution(&q, &q_neg);
+        p = u.into_iter().skip((k % 2) as usize).step_by(2).collect();
+        q = v.into_iter().step_by(2).collect();
+        k /= 2;
+    }
+
+    match p.first() {
+        Some(&p0) if k == 0 => p0 / q[0],
+        _ => T::zero(),
+    }
+}
+
+/// Returns `s[k]` by Kitamasa's method in `O(d^2 log k)`, reducing `x^k`
+/// modulo the characteristic polynomial.
+///
+/// # Panics
+///
+/// Panics if `init` has fewer than `coeffs.len()` terms.
+pub fn kitamasa<T: ModIntLike>(init: &[T], coeffs: &[T], k: u64) -> T {
+    let d = coeffs.len();
+    assert!(init.len() >= d, "need at least {} initial terms", d);
+    if d == 0 {
+        return T::zero();
+    }
+    if k < d as u64 {
+        return init[k as usize];
+    }
+
+    // Multiplies by x, then folds x^d back using the recurrence.
+    let shift = |a: &mut Vec<T>| {
+        let top = a.pop().unwrap();
+        a.insert(0, T::zero());
+        for (x, &c) in a.iter_mut().rev().zip(coeffs) {
+            *x += top * c;
+        }
+    };
+
+    let mut r = vec![T::zero(); d];
+    r[0] = T::one();
+    for bit in (0..64 - k.leading_zeros()).rev() {
+        let mut sq = vec![T::zero(); 2 * d - 1];
+        for (i, &x) in r.iter().enumerate() {
+            for (j, &y) in r.iter().enumerate() {
+                sq[i + j] += x * y;
+            }
+        }
+        for i in (d..2 * d - 1).rev() {
+            let top = sq[i];
+            for (j, &c) in coeffs.iter().enumerate() {
+                sq[i - 1 - j] += top * c;
+            }
+        }
+        sq.truncate(d);
+        r = sq;
+        if k >> bit & 1 == 1 {
+            shift(&mut r);
+        }
+    }
+
+    r.iter().zip(init).map(|(&x, &y)| x * y).sum()
+}
+
+/// Returns `s[k]` for the sequence starting with `init` and following
+/// `coeffs`, using Bostan–Mori when the modulus is NTT-friendly and
+/// [`kitamasa`] otherwise.
+///
+/// # Panics
+///
+/// Panics if `init` has fewer than `coeffs.len()` terms.
+pub fn kth_term<T: ModIntLike>(init: &[T], coeffs: &[T], k: u64) -> T {
+    let d = coeffs.len();
+    assert!(init.len() >= d, "need at least {} initial terms", d);
+    if k < init.len() as u64 {
+        return init[k as usize];
+    }
+    if !supports_ntt::<T>(2 * d + 1) {
+        return kitamasa(init, coeffs, k);
+    }
+
+    // s(x) = p(x) / q(x) with q = 1 - sum c_j x^j and p = s q mod x^d.
+    let mut q = Vec::with_capacity(d + 1);
+    q.push(T::one());
+    q.extend(coeffs.iter().map(|&c| -c));
+    let mut p = convolution(&init[..d], &q);
+    p.truncate(d);
+
+    bostan_mori(&p, &q, k)
+}
+
+/// Guesses the recurrence of `prefix` with [`berlekamp_massey`] and returns
+/// the `k`-th term of the sequence it generates.
+///
+/// # Examples
+///
+/// ```
+/// use algorithm_rs::math::linear_recurrence::guess_kth_term;
+/// use algorithm_rs::math::modint::ModInt1000000007 as Mint;
+///
+/// let s: Vec<Mint> = [0, 1, 1, 2, 3, 5].iter().map(|&x| Mint::new(x)).collect();
+/// assert_eq!(guess_kth_term(&s, 1_000_000_000_000_000_000).val(), 209_783_453);
+/// ```
+pub fn guess_kth_term<T: ModIntLike>(prefix: &[T], k: u64) -> T {
+    let coeffs = berlekamp_massey(prefix);
+    kth_term(prefix, &coeffs, k)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::modint::{ModInt1000000007, ModInt998244353, StaticModInt};
+    use crate::test_util::Rng;
+
+    fn generate<T: ModIntLike>(init: &[T], coeffs: &[T], n: usize) -> Vec<T> {
+        let mut s = init.to_vec();
+        while s.len() < n {
+            let i = s.len();
+            let next = coeffs
+                .iter()
+                .enumerate()
+                .map(|(j, &c)| c * s[i - 1 - j])
+                .sum();
+            s.push(next);
+        }
+        s
+    }
+
+    fn check<T: ModIntLike>(rng: &mut Rng) {
+        for d in [1, 2, 5, 30, 100] {
+            let init: Vec<T> = (0..d).map(|_| T::new(rng.next())).collect();
+            let mut coeffs: Vec<T> = (0..d).map(|_| T::new(rng.next())).collect();
+            coeffs[d - 1] = T::one();
+            let s = generate(&init, &coeffs, 4 * d + 50);
+
+            assert_eq!(berlekamp_massey(&s[..2 * d]), coeffs);
+            for k in [0, d as u64, 2 * d as u64 + 7, 4 * d as u64 + 49] {
+                assert_eq!(kth_term(&init, &coeffs, k), s[k as usize]);
+                assert_eq!(kitamasa(&init, &coeffs, k), s[k as usize]);
+                assert_eq!(guess_kth_term(&s[..2 * d], k), s[k as usize]);
+            }
+        }
+    }
+
+    #[test]
+    fn test_random_recurrences() {
+        let mut rng = Rng(30);
+        check::<ModInt998244353>(&mut rng);
+        check::<ModInt1000000007>(&mut rng);
+    }
+
+    #[test]
+    fn test_berlekamp_massey_edge_cases() {
+        type Mint = StaticModInt<7>;
+        assert!(berlekamp_massey::<Mint>(&[]).is_empty());
+        assert!(berlekamp_massey(&[Mint::new(0); 5]).is_empty());
+        // 1, 0, 0, ... needs order 1 with a zero coefficient.
+        let s = [Mint::new(1), 0.into(), 0.into(), 0.into()];
+        assert_eq!(berlekamp_massey(&s), vec![Mint::new(0)]);
+    }
+
+    #[test]
+    fn test_large_index() {
+        // Fibonacci modulo 998244353 has period 1996488708.
+        let init = [ModInt998244353::new(0), 1.into()];
+        let coeffs = [ModInt998244353::new(1), 1.into()];
+        let a = kth_term(&init, &coeffs, 10u64.pow(18));
+        let b = kth_term(&init, &coeffs, 10u64.pow(18) % 1_996_488_708);
+        assert_eq!(a, b);
+        assert_eq!(kitamasa(&init, &coeffs, 10u64.pow(18)), a);
+    }
+}
@@ -0,0 +1,20 @@
+//! Number theory, algebra and counting.
+
+pub mod combinatorics;
+pub mod convolution;
+pub mod fft;
+pub mod field;
+pub mod floor_sum;
+pub mod fps;
+pub mod linear_recurrence;
+pub mod modint;
+pub mod modular_roots;
+pub mod montgomery;
+pub mod number_theory;
+pub mod polynomial;
+pub mod prime;
+pub mod prime_sum;
+pub mod sequences;
+pub mod sieve;
+
+pub use self::floor_sum::floor_sum;
@@ -0,0 +1,682 @@
+//! Integers modulo `m` with the usual arithmetic operators.
+//!
+//! [`StaticModInt`] fixes the modulus at compile time and [`DynamicModInt`]
+//! reads it at runtime. Generic code such as convolutions or combinatorics
+//! tables is written against [`ModIntLike`] and works with either.
+
+use std::fmt::{self, Debug, Display};
+use std::hash::Hash;
+use std::iter::{Product, Sum};
+use std::marker::PhantomData;
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
+
+/// Integer types that can be reduced modulo a `u32` modulus.
+pub trait RemEuclidU32: Copy {
+    /// Returns the least non-negative residue of `self` modulo `modulus`.
+    fn rem_euclid_u32(self, modulus: u32) -> u32;
+}
+
+macro_rules! impl_rem_euclid_u32 {
+    ($wide:ty; $($t:ty),*) => {
+        $(
+            impl RemEuclidU32 for $t {
+                fn rem_euclid_u32(self, modulus: u32) -> u32 {
+                    (self as $wide).rem_euclid(modulus as $wide) as u32
+                }
+            }
+        )*
+    };
+}
+
+impl_rem_euclid_u32!(u64; u8, u16, u32, u64, usize);
+impl_rem_euclid_u32!(i64; i8, i16, i32, i64, isize);
+impl_rem_euclid_u32!(u128; u128);
+impl_rem_euclid_u32!(i128; i128);
+
+/// Common interface of the modular integer types.
+pub trait ModIntLike:
+    Copy
+    + Eq
+    + Hash
+    + Default
+    + Debug
+    + Display
+    + FromStr<Err = ParseModIntError>
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Neg<Output = Self>
+    + AddAssign
+    + SubAssign
+    + MulAssign
+    + DivAssign
+    + Sum
+    + Product
+    + From<u32>
+    + From<u64>
+    + From<usize>
+    + From<i64>
+{
+    /// Returns the modulus.
+    fn modulus() -> u32;
+
+    /// Wraps `val` without reducing it; `val` must be less than the modulus.
+    fn raw(val: u32) -> Self;
+
+    /// Returns the representative in `0..modulus`.
+    fn val(self) -> u32;
+
+    /// Reduces `val` modulo the modulus.
+    fn new<T: RemEuclidU32>(val: T) -> Self {
+        Self::raw(val.rem_euclid_u32(Self::modulus()))
+    }
+
+    /// Returns zero.
+    fn zero() -> Self {
+        Self::raw(0)
+    }
+
+    /// Returns one.
+    fn one() -> Self {
+        Self::new(1u32)
+    }
+
+    /// Returns `self` raised to the `exp`-th power.
+    fn pow(self, mut exp: u64) -> Self {
+        let mut base = self;
+        let mut result = Self::one();
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result *= base;
+            }
+            base *= base;
+            exp >>= 1;
+        }
+
+        result
+    }
+
+    /// Returns the multiplicative inverse, or `None` if `self` is not coprime to the modulus.
+    fn checked_inv(self) -> Option<Self> {
+        let (gcd, inv) = inv_gcd(self.val() as i64, Self::modulus() as i64);
+        (gcd == 1).then(|| Self::raw(inv as u32))
+    }
+
+    /// Returns the multiplicative inverse.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `self` is not coprime to the modulus.
+    fn inv(self) -> Self {
+        self.checked_inv()
+            .unwrap_or_else(|| panic!("{} is not invertible modulo {}", self, Self::modulus()))
+    }
+}
+
+/// Returns `(g, x)` with `g = gcd(a, b)` and `a * x = g (mod b)`, `0 <= x < b / g`.
+pub(crate) fn inv_gcd(a: i64, b: i64) -> (i64, i64) {
+    let a = a.rem_euclid(b);
+    if a == 0 {
+        return (b, 0);
+    }
+
+    let (mut s, mut t) = (b, a);
+    let (mut m0, mut m1) = (0, 1);
+    while t != 0 {
+        let u = s / t;
+        s -= t * u;
+        m0 -= m1 * u;
+        std::mem::swap(&mut s, &mut t);
+        std::mem::swap(&mut m0, &mut m1);
+    }
+    if m0 < 0 {
+        m0 += b / s;
+    }
+
+    (s, m0)
+}
+
+/// Error returned when parsing a modular integer from a string fails.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseModIntError;
+
+impl Display for ParseModIntError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid digit found in string")
+    }
+}
+
+impl std::error::Error for ParseModIntError {}
+
+/// An integer modulo the compile-time constant `M`, which must be at least 1.
+///
+/// # Examples
+///
+/// ```
+/// use algorithm_rs::math::modint::ModInt998244353 as Mint;
+///
+/// let a = Mint::new(-1);
+/// assert_eq!(a.val(), 998_244_352);
+/// assert_eq!((a * a).val(), 1);
+/// assert_eq!(Mint::new(2).inv() * 2, Mint::new(1));
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct StaticModInt<const M: u32> {
+    val: u32,
+}
+
+/// Integers modulo the NTT-friendly prime 998244353.
+pub type ModInt998244353 = StaticModInt<998_244_353>;
+/// Integers modulo the prime 1000000007.
+pub type ModInt1000000007 = StaticModInt<1_000_000_007>;
+
+impl<const M: u32> StaticModInt<M> {
+    /// Returns the modulus `M`.
+    pub fn modulus() -> u32 {
+        M
+    }
+
+    /// Wraps `val` without reducing it; `val` must be less than `M`.
+    pub fn raw(val: u32) -> Self {
+        Self { val }
+    }
+
+    /// Returns the representative in `0..M`.
+    pub fn val(self) -> u32 {
+        self.val
+    }
+
+    fn mul_mod(a: u32, b: u32) -> u32 {
+        (a as u64 * b as u64 % M as u64) as u32
+    }
+}
+
+impl<const M: u32> ModIntLike for StaticModInt<M> {
+    fn modulus() -> u32 {
+        M
+    }
+
+    fn raw(val: u32) -> Self {
+        Self { val }
+    }
+
+    fn val(self) -> u32 {
+        self.val
+    }
+}
+
+/// Precomputed constants for Barrett reduction modulo a runtime `u32` modulus.
+///
+/// The fields are atomics only so that a `Barrett` can live in a `static`;
+/// all accesses are relaxed loads, which compile to plain loads.
+pub struct Barrett {
+    m: AtomicU32,
+    im: AtomicU64,
+}
+
+impl Barrett {
+    /// Creates the constants for modulus `m`, which must be at least 1.
+    pub const fn new(m: u32) -> Self {
+        Self {
+            m: AtomicU32::new(m),
+            im: AtomicU64::new((u64::MAX / m as u64).wrapping_add(1)),
+        }
+    }
+
+    fn set(&self, m: u32) {
+        self.m.store(m, Ordering::Relaxed);
+        self.im
+            .store((u64::MAX / m as u64).wrapping_add(1), Ordering::Relaxed);
+    }
+
+    /// Returns the modulus.
+    pub fn modulus(&self) -> u32 {
+        self.m.load(Ordering::Relaxed)
+    }
+
+    /// Returns `a * b % m` for `a, b < m`.
+    pub fn mul(&self, a: u32, b: u32) -> u32 {
+        let m = self.modulus() as u64;
+        let im = self.im.load(Ordering::Relaxed);
+
+        let z = a as u64 * b as u64;
+        let x = ((z as u128 * im as u128) >> 64) as u64;
+        let (r, borrow) = z.overflowing_sub(x * m);
+        (if borrow { r.wrapping_add(m) } else { r }) as u32
+    }
+}
+
+/// A tag type selecting the modulus storage of a [`DynamicModInt`].
+///
+/// Distinct tags let several runtime moduli coexist:
+///
+/// ```
+/// use algorithm_rs::math::modint::{Barrett, DynamicModInt, ModIntId};
+///
+/// #[derive(Clone, Copy, PartialEq, Eq, Hash, Default, Debug)]
+/// struct Second;
+///
+/// impl ModIntId for Second {
+///     fn barrett() -> &'static Barrett {
+///         static BARRETT: Barrett = Barrett::new(998_244_353);
+///         &BARRETT
+///     }
+/// }
+///
+/// DynamicModInt::<Second>::set_modulus(7);
+/// assert_eq!(DynamicModInt::<Second>::new(10).val(), 3);
+/// ```
+pub trait ModIntId: 'static + Copy + Eq + Hash + Default + Debug {
+    /// Returns the reduction constants shared by all values with this tag.
+    fn barrett() -> &'static Barrett;
+}
+
+/// The tag used by [`ModInt`].
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default, Debug)]
+pub struct DefaultId;
+
+impl ModIntId for DefaultId {
+    fn barrett() -> &'static Barrett {
+        static BARRETT: Barrett = Barrett::new(998_244_353);
+        &BARRETT
+    }
+}
+
+/// An integer modulo a runtime modulus, shared by all values with tag `I`.
+///
+/// The modulus defaults to 998244353 until [`set_modulus`](Self::set_modulus)
+/// is called. Changing it invalidates existing values of the same tag.
+///
+/// # Examples
+///
+/// ```
+/// use algorithm_rs::math::modint::ModInt;
+///
+/// ModInt::set_modulus(13);
+/// assert_eq!((ModInt::new(5) * 8).val(), 1);
+/// assert_eq!(ModInt::new(5).inv().val(), 8);
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct DynamicModInt<I: ModIntId> {
+    val: u32,
+    phantom: PhantomData<fn() -> I>,
+}
+
+/// Integers modulo a runtime modulus with the [`DefaultId`] tag.
+pub type ModInt = DynamicModInt<DefaultId>;
+
+impl<I: ModIntId> DynamicModInt<I> {
+    /// Sets the modulus for all values with tag `I`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `modulus` is zero.
+    pub fn set_modulus(modulus: u32) {
+        assert!(modulus >= 1, "modulus must be at least 1");
+        I::barrett().set(modulus);
+    }
+
+    /// Returns the current modulus.
+    pub fn modulus() -> u32 {
+        I::barrett().modulus()
+    }
+
+    /// Wraps `val` without reducing it; `val` must be less than the modulus.
+    pub fn raw(val: u32) -> Self {
+        Self {
+            val,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Returns the representative in `0..modulus`.
+    pub fn val(self) -> u32 {
+        self.val
+    }
+
+    fn mul_mod(a: u32, b: u32) -> u32 {
+        I::barrett().mul(a, b)
+    }
+}
+
+impl<I: ModIntId> ModIntLike for DynamicModInt<I> {
+    fn modulus() -> u32 {
+        Self::modulus()
+    }
+
+    fn raw(val: u32) -> Self {
+        Self::raw(val)
+    }
+
+    fn val(self) -> u32 {
+        self.val
+    }
+}
+
+macro_rules! impl_modint {
+    ([$($generics:tt)*] $t:ty) => {
+        impl<$($generics)*> $t {
+            /// Reduces `val` modulo the modulus.
+            pub fn new<T: RemEuclidU32>(val: T) -> Self {
+                <Self as ModIntLike>::new(val)
+            }
+
+            /// Returns `self` raised to the `exp`-th power.
+            pub fn pow(self, exp: u64) -> Self {
+                <Self as ModIntLike>::pow(self, exp)
+            }
+
+            /// Returns the multiplicative inverse.
+            ///
+            /// # Panics
+            ///
+            /// Panics if `self` is not coprime to the modulus.
+            pub fn inv(self) -> Self {
+                <Self as ModIntLike>::inv(self)
+            }
+
+            /// Returns the multiplicative inverse, or `None` if `self` is not coprime to the modulus.
+            pub fn checked_inv(self) -> Option<Self> {
+                <Self as ModIntLike>::checked_inv(self)
+            }
+        }
+
+        impl<$($generics)*> $t {
+            fn add_impl(self, rhs: Self) -> Self {
+                let sum = self.val() as u64 + rhs.val() as u64;
+                let modulus = Self::modulus() as u64;
+                Self::raw(if sum >= modulus { sum - modulus } else { sum } as u32)
+            }
+
+            fn sub_impl(self, rhs: Self) -> Self {
+                let (diff, borrow) = self.val().overflowing_sub(rhs.val());
+                Self::raw(if borrow { diff.wrapping_add(Self::modulus()) } else { diff })
+            }
+
+            fn mul_impl(self, rhs: Self) -> Self {
+                Self::raw(Self::mul_mod(self.val(), rhs.val()))
+            }
+
+            fn div_impl(self, rhs: Self) -> Self {
+                self * rhs.inv()
+            }
+        }
+
+        impl<$($generics)*> Neg for $t {
+            type Output = Self;
+
+            fn neg(self) -> Self {
+                Self::raw(0).sub_impl(self)
+            }
+        }
+
+        impl_modint!(@binary [$($generics)*] $t; Add, add, add_impl, AddAssign, add_assign);
+        impl_modint!(@binary [$($generics)*] $t; Sub, sub, sub_impl, SubAssign, sub_assign);
+        impl_modint!(@binary [$($generics)*] $t; Mul, mul, mul_impl, MulAssign, mul_assign);
+        impl_modint!(@binary [$($generics)*] $t; Div, div, div_impl, DivAssign, div_assign);
+
+        impl<$($generics)*, V: RemEuclidU32> From<V> for $t {
+            fn from(val: V) -> Self {
+                Self::new(val)
+            }
+        }
+
+        impl<$($generics)*> Neg for &$t {
+            type Output = $t;
+
+            fn neg(self) -> $t {
+                -*self
+            }
+        }
+
+        impl<$($generics)*> Sum for $t {
+            fn sum<It: Iterator<Item = Self>>(iter: It) -> Self {
+                iter.fold(Self::raw(0), Self::add_impl)
+            }
+        }
+
+        impl<'a, $($generics)*> Sum<&'a Self> for $t {
+            fn sum<It: Iterator<Item = &'a Self>>(iter: It) -> Self {
+                iter.copied().sum()
+            }
+        }
+
+        impl<$($generics)*> Product for $t {
+            fn product<It: Iterator<Item = Self>>(iter: It) -> Self {
+                iter.fold(<Self as ModIntLike>::one(), Self::mul_impl)
+            }
+        }
+
+        impl<'a, $($generics)*> Product<&'a Self> for $t {
+            fn product<It: Iterator<Item = &'a Self>>(iter: It) -> Self {
+                iter.copied().product()
+            }
+        }
+
+        impl<$($generics)*> Display for $t {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                Display::fmt(&self.val(), f)
+            }
+        }
+
+        impl<$($generics)*> Debug for $t {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                Debug::fmt(&self.val(), f)
+            }
+        }
+
+        /// Parses a decimal integer of any length, reducing it modulo the modulus.
+        impl<$($generics)*> FromStr for $t {
+            type Err = ParseModIntError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                let (negative, digits) = match s.strip_prefix('-') {
+                    Some(rest) => (true, rest),
+                    None => (false, s.strip_prefix('+').unwrap_or(s)),
+                };
+                if digits.is_empty() || !digits.bytes().all(|c| c.is_ascii_digit()) {
+                    return Err(ParseModIntError);
+                }
+
+                let ten = Self::from(10u32);
+                let value = digits
+                    .bytes()
+                    .fold(Self::raw(0), |acc, c| acc * ten + Self::from(c - b'0'));
+                Ok(if negative { -value } else { value })
+            }
+        }
+    };
+    (@binary [$($generics:tt)*] $t:ty; $op:ident, $method:ident, $inner:ident, $op_assign:ident, $method_assign:ident) => {
+        impl<$($generics)*, T: Into<$t>> $op<T> for $t {
+            type Output = $t;
+
+            fn $method(self, rhs: T) -> $t {
+                self.$inner(rhs.into())
+            }
+        }
+
+        impl<'a, $($generics)*> $op<&'a $t> for $t {
+            type Output = $t;
+
+            fn $method(self, rhs: &'a $t) -> $t {
+                self.$inner(*rhs)
+            }
+        }
+
+        impl<$($generics)*, T: Into<$t>> $op<T> for &$t {
+            type Output = $t;
+
+            fn $method(self, rhs: T) -> $t {
+                self.$inner(rhs.into())
+            }
+        }
+
+        impl<'a, $($generics)*> $op<&'a $t> for &$t {
+            type Output = $t;
+
+            fn $method(self, rhs: &'a $t) -> $t {
+                self.$inner(*rhs)
+            }
+        }
+
+        impl<$($generics)*, T: Into<$t>> $op_assign<T> for $t {
+            fn $method_assign(&mut self, rhs: T) {
+                *self = self.$inner(rhs.into());
+            }
+        }
+
+        impl<'a, $($generics)*> $op_assign<&'a $t> for $t {
+            fn $method_assign(&mut self, rhs: &'a $t) {
+                *self = self.$inner(*rhs);
+            }
+        }
+    };
+}
+
+impl_modint!([const M: u32] StaticModInt<M>);
+impl_modint!([I: ModIntId] DynamicModInt<I>);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type Mint = ModInt1000000007;
+
+    #[test]
+    fn test_arithmetic() {
+        let a = Mint::new(1_000_000_006);
+        let b = Mint::new(5);
+
+        assert_eq!((a + b).val(), 4);
+        assert_eq!((b - a).val(), 6);
+        assert_eq!((a * a).val(), 1);
+        assert_eq!((-b).val(), 1_000_000_002);
+        assert_eq!(b / b, Mint::new(1));
+        assert_eq!(Mint::new(3) / Mint::new(2) * 2, Mint::new(3));
+
+        let mut c = a;
+        c += b;
+        c -= &b;
+        c *= 3;
+        c /= Mint::new(3);
+        assert_eq!(c, a);
+    }
+
+    #[test]
+    fn test_conversions() {
+        assert_eq!(Mint::new(-1i64).val(), 1_000_000_006);
+        assert_eq!(
+            Mint::from(i128::MIN).val(),
+            i128::MIN.rem_euclid(1_000_000_007) as u32
+        );
+        assert_eq!(
+            Mint::from(u64::MAX).val(),
+            (u64::MAX % 1_000_000_007) as u32
+        );
+        assert_eq!(Mint::from(7u8), Mint::from(-1_000_000_000i32));
+        assert_eq!(StaticModInt::<1>::new(12345).val(), 0);
+    }
+
+    #[test]
+    fn test_pow_and_inv() {
+        assert_eq!(Mint::new(2).pow(10).val(), 1024);
+        assert_eq!(Mint::new(0).pow(0).val(), 1);
+        for x in 1..100u32 {
+            let x = Mint::new(x);
+            assert_eq!(x * x.inv(), Mint::new(1));
+        }
+
+        type Composite = StaticModInt<12>;
+        assert_eq!(Composite::new(5).inv().val(), 5);
+        assert_eq!(Composite::new(4).checked_inv(), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_inv_zero() {
+        Mint::new(0).inv();
+    }
+
+    #[test]
+    fn test_large_modulus() {
+        type Big = StaticModInt<4_294_967_291>;
+        let a = Big::new(4_294_967_290u32);
+
+        assert_eq!((a + a).val(), 4_294_967_289);
+        assert_eq!((Big::new(1) - a).val(), 2);
+        assert_eq!((a * a).val(), 1);
+    }
+
+    #[test]
+    fn test_parse_and_format() {
+        assert_eq!("123".parse::<Mint>().unwrap().val(), 123);
+        assert_eq!("-1".parse::<Mint>().unwrap().val(), 1_000_000_006);
+        assert_eq!(
+            "100000000000000000000".parse::<Mint>().unwrap(),
+            Mint::new(10).pow(20)
+        );
+        assert_eq!("12a".parse::<Mint>(), Err(ParseModIntError));
+        assert_eq!("".parse::<Mint>(), Err(ParseModIntError));
+        assert_eq!(
+            format!("{} {:?}", Mint::new(-2), Mint::new(3)),
+            "1000000005 3"
+        );
+    }
+
+    #[test]
+    fn test_sum_and_product() {
+        let values: Vec<Mint> = (1..=10u32).map(Mint::new).collect();
+
+        assert_eq!(values.iter().sum::<Mint>().val(), 55);
+        assert_eq!(values.into_iter().product::<Mint>().val(), 3_628_800);
+    }
+
+    #[derive(Clone, Copy, PartialEq, Eq, Hash, Default, Debug)]
+    struct TestId;
+
+    impl ModIntId for TestId {
+        fn barrett() -> &'static Barrett {
+            static BARRETT: Barrett = Barrett::new(1);
+            &BARRETT
+        }
+    }
+
+    fn factorial<T: ModIntLike>(n: u32) -> T {
+        (1..=n).map(T::from).product()
+    }
+
+    #[test]
+    fn test_barrett() {
+        for m in [1, 2, 3, 998_244_353, 1_000_000_007, 2_147_483_647, u32::MAX] {
+            let barrett = Barrett::new(m);
+            for &(a, b) in &[
+                (0, 0),
+                (1 % m, 1 % m),
+                (m - 1, m - 1),
+                (m / 2, m - 1),
+                (12345 % m, 67890 % m),
+            ] {
+                assert_eq!(barrett.mul(a, b) as u64, a as u64 * b as u64 % m as u64);
+            }
+        }
+    }
+
+    #[test]
+    fn test_dynamic() {
+        type Dyn = DynamicModInt<TestId>;
+
+        Dyn::set_modulus(1_000_000_007);
+        assert_eq!(Dyn::modulus(), 1_000_000_007);
+        assert_eq!(factorial::<Dyn>(20).val(), factorial::<Mint>(20).val());
+        assert_eq!((Dyn::new(-1) * Dyn::new(-1)).val(), 1);
+        assert_eq!("-5".parse::<Dyn>().unwrap() + 5, Dyn::new(0));
+
+        Dyn::set_modulus(10);
+        assert_eq!(Dyn::new(3).inv().val(), 7);
+        assert_eq!(Dyn::new(4).checked_inv(), None);
+        assert_eq!(Dyn::new(7).pow(4).val(), 1);
+    }
+}
@@ -0,0 +1,362 @@
+//! Discrete logarithms, square roots and `k`-th roots modulo an integer.
+//!
+//! All functions take plain `u64` residues and multiply through `u128`.
+
+use crate::math::number_theory::ext_gcd;
+use crate::math::prime::factorize;
+use std::collections::HashMap;
+
+fn mul_mod(a: u64, b: u64, m: u64) -> u64 {
+    (a as u128 * b as u128 % m as u128) as u64
+}
+
+fn pow_mod(mut a: u64, mut exp: u64, m: u64) -> u64 {
+    let mut result = 1 % m;
+    a %= m;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = mul_mod(result, a, m);
+        }
+        a = mul_mod(a, a, m);
+        exp >>= 1;
+    }
+    result
+}
+
+/// Returns the inverse of `a` modulo `m`, for `a` coprime to `m`.
+fn inv(a: u64, m: u64) -> u64 {
+    let (_, x, _) = ext_gcd(a as i128, m as i128);
+    x.rem_euclid(m as i128) as u64
+}
+
+fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// Returns the smallest `x >= 0` with `a^x = b (mod m)`, if any.
+///
+/// Runs baby-step giant-step in `O(sqrt(m))`. A base sharing factors with
+/// `m` is handled by first dividing those factors out, so for example
+/// `2^x = 0 (mod 8)` yields `3`. Takes `0^0 = 1`.
+///
+/// # Panics
+///
+/// Panics if `m` is zero.
+///
+/// # Examples
+///
+/// ```
+/// use algorithm_rs::math::modular_roots::discrete_log;
+///
+/// assert_eq!(discrete_log(3, 13, 17), Some(4));
+/// assert_eq!(discrete_log(2, 0, 8), Some(3));
+/// assert_eq!(discrete_log(2, 3, 8), None);
+/// ```
+pub fn discrete_log(a: u64, b: u64, m: u64) -> Option<u64> {
+    assert!(m > 0, "modulus must be positive");
+    let (mut a, mut b, mut m) = (a % m, b % m, m);
+
+    // Peel off a^k until a is a unit: k a^x = b (mod m) with gcd(a, m) = 1.
+    let mut k = 1 % m;
+    let mut offset = 0;
+    loop {
+        if b == k {
+            return Some(offset);
+        }
+        let g = gcd(a, m);
+        if g == 1 {
+            break;
+        }
+        if !b.is_multiple_of(g) {
+            return None;
+        }
+        b /= g;
+        m /= g;
+        offset += 1;
+        k = mul_mod(k, a / g, m);
+        a %= m;
+        b %= m;
+    }
+
+    // Baby steps store b a^j for the largest j; giant steps try k a^(n i).
+    let n = m.isqrt() + 1;
+    let mut baby = HashMap::with_capacity(n as usize);
+    let mut cur = b;
+    for j in 0..n {
+        baby.insert(cur, j);
+        cur = mul_mod(cur, a, m);
+    }
+
+    let giant = pow_mod(a, n, m);
+    let mut cur = k;
+    for i in 1..=n {
+        cur = mul_mod(cur, giant, m);
+        if let Some(&j) = baby.get(&cur) {
+            return Some(n * i - j + offset);
+        }
+    }
+
+    None
+}
+
+/// Returns a square root of `a` modulo the prime `p`, if any, choosing the
+/// smaller of the two roots.
+///
+/// Uses Tonelli–Shanks in `O(log^2 p)`.
+///
+/// # Examples
+///
+/// ```
+/// use algorithm_rs::math::modular_roots::sqrt_mod;
+///
+/// assert_eq!(sqrt_mod(2, 7), Some(3));
+/// assert_eq!(sqrt_mod(3, 7), None);
+/// assert_eq!(sqrt_mod(0, 998_244_353), Some(0));
+/// ```
+pub fn sqrt_mod(a: u64, p: u64) -> Option<u64> {
+    let a = a % p;
+    if a < 2 || p == 2 {
+        return Some(a);
+    }
+    if pow_mod(a, (p - 1) / 2, p) != 1 {
+        return None;
+    }
+
+    // p - 1 = q 2^s with z a non-residue.
+    let s = (p - 1).trailing_zeros();
+    let q = (p - 1) >> s;
+    let z = (2..p).find(|&z| pow_mod(z, (p - 1) / 2, p) == p - 1)?;
+    let mut c = pow_mod(z, q, p);
+    let mut x = pow_mod(a, q.div_ceil(2), p);
+    let mut t = pow_mod(a, q, p);
+    let mut m = s;
+    while t != 1 {
+        let mut i = 0;
+        let mut t2 = t;
+        while t2 != 1 {
+            t2 = mul_mod(t2, t2, p);
+            i += 1;
+        }
+        let b = pow_mod(c, 1 << (m - i - 1), p);
+        x = mul_mod(x, b, p);
+        c = mul_mod(b, b, p);
+        t = mul_mod(t, c, p);
+        m = i;
+    }
+
+    Some(x.min(p - x))
+}
+
+/// Returns some `x` with `x^k = a (mod p)` for the prime `p`, if any.
+///
+/// Reduces to `gcd(k, p - 1)`-th roots and takes them one prime factor `q`
+/// at a time by Adleman–Manders–Miller, where the correction within the
+/// Sylow `q`-subgroup is found by a Pohlig–Hellman discrete log. The cost
+/// is dominated by `O(sqrt(q))` per prime factor `q` of `gcd(k, p - 1)`.
+/// Takes `0^0 = 1`.
+///
+/// # Examples
+///
+/// ```
+/// use algorithm_rs::math::modular_roots::kth_root_mod;
+///
+/// let x = kth_root_mod(5, 3, 13).unwrap();
+/// assert_eq!(x * x * x % 13, 5);
+/// assert_eq!(kth_root_mod(2, 3, 13), None);
+/// ```
+pub fn kth_root_mod(a: u64, k: u64, p: u64) -> Option<u64> {
+    let a = a % p;
+    if k == 0 {
+        return (a == 1 % p).then_some(1 % p);
+    }
+    if a == 0 || p == 2 {
+        return Some(a);
+    }
+
+    let g = gcd(k, p - 1);
+    if pow_mod(a, (p - 1) / g, p) != 1 {
+        return None;
+    }
+
+    // With y^g = a, x = y^(k/g)^-1 works, exponents being taken mod (p-1)/g.
+    let mut y = a;
+    for (q, e) in factorize(g) {
+        for _ in 0..e {
+            y = prime_root(y, q, p);
+        }
+    }
+    let order = (p - 1) / g;
+    let u = inv((k / g) % order, order);
+    Some(pow_mod(y, u, p))
+}
+
+/// Returns a `q`-th root of the `q`-th power residue `a` modulo `p`, for a
+/// prime `q` dividing `p - 1`.
+fn prime_root(a: u64, q: u64, p: u64) -> u64 {
+    // p - 1 = q^s t with q coprime to t.
+    let mut s = 0;
+    let mut t = p - 1;
+    while t.is_multiple_of(q) {
+        t /= q;
+        s += 1;
+    }
+
+    // x = a^(q^-1 mod t) has x^q = a * err with err in the order q^(s-1) subgroup.
+    let alpha = inv(q % t, t);
+    let mut x = pow_mod(a, alpha, p);
+    if s == 1 {
+        return x;
+    }
+    let err = mul_mod(pow_mod(x, q, p), inv(a, p), p);
+
+    // c generates the Sylow q-subgroup; write err = c^j, then divide x by c^(j/q).
+    let rho = (2..p).find(|&r| pow_mod(r, (p - 1) / q, p) != 1).unwrap();
+    let c = pow_mod(rho, t, p);
+    let j = sylow_log(c, err, q, s, p);
+    let fix = pow_mod(c, (q.pow(s) - j / q) % q.pow(s), p);
+    x = mul_mod(x, fix, p);
+    x
+}
+
+/// Returns `j` with `c^j = h`, where `c` has order `q^s` and `h` lies in its group.
+fn sylow_log(c: u64, h: u64, q: u64, s: u32, p: u64) -> u64 {
+    // gamma has order q; each base-q digit of j is a log to base gamma.
+    let gamma = pow_mod(c, q.pow(s - 1), p);
+    let n = q.isqrt() + 1;
+    let mut baby = HashMap::with_capacity(n as usize);
+    let mut cur = 1;
+    for i in 0..n {
+        baby.entry(cur).or_insert(i);
+        cur = mul_mod(cur, gamma, p);
+    }
+    let giant = inv(pow_mod(gamma, n, p), p);
+    let digit_log = |target: u64| {
+        let mut cur = target;
+        for i in 0..n {
+            if let Some(&b) = baby.get(&cur) {
+                return i * n + b;
+            }
+            cur = mul_mod(cur, giant, p);
+        }
+        unreachable!("element outside the subgroup")
+    };
+
+    let c_inv = inv(c, p);
+    let mut j = 0;
+    let mut power = 1;
+    for i in 0..s {
+        // (h c^-j)^(q^(s-1-i)) = gamma^digit
+        let rest = mul_mod(h, pow_mod(c_inv, j, p), p);
+        let digit = digit_log(pow_mod(rest, q.pow(s - 1 - i), p));
+        j += digit * power;
+        power *= q;
+    }
+
+    j
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::Rng;
+
+    const PRIMES: [u64; 12] = [2, 3, 5, 7, 13, 17, 31, 41, 97, 101, 257, 433];
+
+    #[test]
+    fn test_discrete_log_brute_force() {
+        for m in 1..=80u64 {
+            for a in 0..m {
+                // a^x is periodic with period at most m from x = log2(m) on.
+                let powers: Vec<u64> = (0..2 * m + 8).map(|x| pow_mod(a, x, m)).collect();
+                for b in 0..m {
+                    let expected = powers.iter().position(|&v| v == b).map(|x| x as u64);
+                    assert_eq!(discrete_log(a, b, m), expected, "{}^x = {} mod {}", a, b, m);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_discrete_log_random() {
+        let mut rng = Rng(60);
+        for _ in 0..100 {
+            let m = rng.next() % 1_000_000_000 + 1;
+            let a = rng.next() % m;
+            let x = rng.next() % 1_000_000;
+            let b = pow_mod(a, x, m);
+            let found = discrete_log(a, b, m).unwrap();
+            assert!(found <= x);
+            assert_eq!(pow_mod(a, found, m), b);
+        }
+    }
+
+    #[test]
+    fn test_sqrt_mod() {
+        for p in PRIMES {
+            for a in 0..p {
+                let roots: Vec<u64> = (0..p).filter(|&x| x * x % p == a).collect();
+                assert_eq!(
+                    sqrt_mod(a, p),
+                    roots.first().copied(),
+                    "sqrt {} mod {}",
+                    a,
+                    p
+                );
+            }
+        }
+
+        let mut rng = Rng(61);
+        let p = 1_000_000_000_000_000_003;
+        for _ in 0..100 {
+            let x = rng.next() % p;
+            let r = sqrt_mod(mul_mod(x, x, p), p).unwrap();
+            assert!(r == x || r == p - x);
+        }
+    }
+
+    #[test]
+    fn test_kth_root_mod() {
+        let mut rng = Rng(62);
+        for p in PRIMES {
+            for k in (0..40).chain([p - 1, p, p + 1, 2 * p - 2]) {
+                let mut exists = vec![false; p as usize];
+                for x in 0..p {
+                    exists[pow_mod(x, k, p) as usize] = true;
+                }
+                for a in 0..p {
+                    let exists = exists[a as usize];
+                    match kth_root_mod(a, k, p) {
+                        Some(x) => {
+                            assert_eq!(pow_mod(x, k, p), a, "{}-th root of {} mod {}", k, a, p)
+                        }
+                        None => assert!(!exists, "{}-th root of {} mod {}", k, a, p),
+                    }
+                }
+            }
+        }
+
+        // p - 1 is 2^23 * 7 * 17, 2 * 500000003, 2^25 * 5, 2^2 * 3^6 * 5^3 * 7^2
+        // and 2 * 3^2 * 5^2 * 7 * 11 * 13 * 31 * 41 * 61 * 151 * 331 * 1321.
+        // The last two have repeated odd prime factors, so multiples of 9, 25
+        // and 49 run the Sylow logs with q > 2 and s > 1.
+        for p in [
+            998_244_353u64,
+            1_000_000_007,
+            167_772_161,
+            17_860_501,
+            2_305_843_009_213_693_951,
+        ] {
+            let mut ks: Vec<u64> = (0..30).map(|_| rng.next() % 1_000_000 + 1).collect();
+            ks.extend([9, 25, 49, 27 * 125, 729 * 125 * 49]);
+            for k in ks {
+                let x = rng.next() % p;
+                let a = pow_mod(x, k, p);
+                let y = kth_root_mod(a, k, p).unwrap();
+                assert_eq!(pow_mod(y, k, p), a);
+            }
+        }
+    }
+}
@@ -0,0 +1,199 @@
+//! Montgomery multiplication for 64-bit odd moduli.
+//!
+//! Products are reduced with two multiplications and a subtraction instead of
+//! a `u128` division, which makes modular exponentiation several times
+//! faster. This backs primality testing and factorization of `u64` values.
+
+/// Reduction context for an odd modulus `n < 2^63`.
+///
+/// Values in Montgomery form represent `a` as `a * 2^64 mod n`. Convert with
+/// [`to_mont`](Self::to_mont) and [`from_mont`](Self::from_mont); `add`,
+/// `sub`, `mul` and `pow` take and return values in Montgomery form.
+///
+/// # Examples
+///
+/// ```
+/// use algorithm_rs::math::montgomery::Montgomery64;
+///
+/// let mont = Montgomery64::new(1_000_000_007);
+/// let a = mont.to_mont(123_456_789);
+/// let b = mont.to_mont(987_654_321);
+/// assert_eq!(mont.from_mont(mont.mul(a, b)), 259_106_859);
+/// assert_eq!(mont.pow_mod(2, 1_000_000_006), 1);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Montgomery64 {
+    n: u64,
+    n_inv: u64,
+    r2: u64,
+}
+
+impl Montgomery64 {
+    /// Creates the context for modulus `n`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is even or at least `2^63`.
+    pub fn new(n: u64) -> Self {
+        assert!(
+            n % 2 == 1 && n < 1 << 63,
+            "modulus must be odd and below 2^63"
+        );
+
+        // Newton's iteration doubles the number of correct low bits each step.
+        let mut n_inv = n;
+        for _ in 0..5 {
+            n_inv = n_inv.wrapping_mul(2u64.wrapping_sub(n.wrapping_mul(n_inv)));
+        }
+
+        let r = ((1u128 << 64) % n as u128) as u64;
+        let r2 = (r as u128 * r as u128 % n as u128) as u64;
+
+        Self { n, n_inv, r2 }
+    }
+
+    /// Returns the modulus.
+    pub fn modulus(&self) -> u64 {
+        self.n
+    }
+
+    /// Returns `t / 2^64 mod n` for `t < n * 2^64`.
+    fn reduce(&self, t: u128) -> u64 {
+        let m = (t as u64).wrapping_mul(self.n_inv);
+        let t_hi = (t >> 64) as u64;
+        let mn_hi = ((m as u128 * self.n as u128) >> 64) as u64;
+
+        if t_hi >= mn_hi {
+            t_hi - mn_hi
+        } else {
+            t_hi + self.n - mn_hi
+        }
+    }
+
+    /// Converts `a` into Montgomery form.
+    pub fn to_mont(&self, a: u64) -> u64 {
+        self.reduce((a % self.n) as u128 * self.r2 as u128)
+    }
+
+    /// Converts `a` out of Montgomery form.
+    pub fn from_mont(&self, a: u64) -> u64 {
+        self.reduce(a as u128)
+    }
+
+    /// Returns one in Montgomery form.
+    pub fn one(&self) -> u64 {
+        self.to_mont(1)
+    }
+
+    /// Returns `a + b` in Montgomery form.
+    pub fn add(&self, a: u64, b: u64) -> u64 {
+        let sum = a + b;
+        if sum >= self.n {
+            sum - self.n
+        } else {
+            sum
+        }
+    }
+
+    /// Returns `a - b` in Montgomery form.
+    pub fn sub(&self, a: u64, b: u64) -> u64 {
+        if a >= b {
+            a - b
+        } else {
+            a + self.n - b
+        }
+    }
+
+    /// Returns `a * b` in Montgomery form.
+    pub fn mul(&self, a: u64, b: u64) -> u64 {
+        self.reduce(a as u128 * b as u128)
+    }
+
+    /// Returns `a^exp` in Montgomery form.
+    pub fn pow(&self, mut a: u64, mut exp: u64) -> u64 {
+        let mut result = self.one();
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result = self.mul(result, a);
+            }
+            a = self.mul(a, a);
+            exp >>= 1;
+        }
+
+        result
+    }
+
+    /// Returns `a * b mod n` for ordinary residues.
+    pub fn mul_mod(&self, a: u64, b: u64) -> u64 {
+        self.from_mont(self.mul(self.to_mont(a), self.to_mont(b)))
+    }
+
+    /// Returns `base^exp mod n` for an ordinary residue.
+    pub fn pow_mod(&self, base: u64, exp: u64) -> u64 {
+        self.from_mont(self.pow(self.to_mont(base), exp))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn naive_pow(mut a: u64, mut exp: u64, n: u64) -> u64 {
+        let mut result = 1 % n;
+        a %= n;
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result = (result as u128 * a as u128 % n as u128) as u64;
+            }
+            a = (a as u128 * a as u128 % n as u128) as u64;
+            exp >>= 1;
+        }
+
+        result
+    }
+
+    #[test]
+    fn test_against_u128() {
+        let moduli = [
+            1,
+            3,
+            101,
+            998_244_353,
+            (1 << 61) - 1,
+            (1 << 63) - 25,
+            (1 << 63) - 1,
+        ];
+        let mut state = 0x9e37_79b9_7f4a_7c15_u64;
+
+        for &n in &moduli {
+            let mont = Montgomery64::new(n);
+            for _ in 0..200 {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                let (a, b) = (state % n, state.rotate_left(32) % n);
+
+                let expected = (a as u128 * b as u128 % n as u128) as u64;
+                assert_eq!(mont.mul_mod(a, b), expected);
+                assert_eq!(mont.from_mont(mont.to_mont(a)), a);
+                assert_eq!(mont.pow_mod(a, b), naive_pow(a, b, n));
+
+                let (x, y) = (mont.to_mont(a), mont.to_mont(b));
+                assert_eq!(
+                    mont.from_mont(mont.add(x, y)),
+                    ((a as u128 + b as u128) % n as u128) as u64
+                );
+                assert_eq!(
+                    mont.from_mont(mont.sub(x, y)),
+                    ((a as u128 + n as u128 - b as u128) % n as u128) as u64
+                );
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_even_modulus() {
+        Montgomery64::new(1 << 32);
+    }
+}
@@ -0,0 +1,269 @@
+//! Extended Euclid, modular inverses and the Chinese remainder theorem.
+//!
+//! Moduli are `i64` or `u64`; intermediate products use `i128`/`u128`, so
+//! no function overflows on valid input. Unsolvable inputs give `None`.
+
+use std::ops::{Div, Mul, Neg, Rem, Sub};
+
+/// Returns `(g, x, y)` with `a x + b y = g = gcd(a, b)` and `g >= 0`.
+///
+/// Works for every signed primitive integer type. When both inputs are
+/// nonzero, `|x| <= |b / g|` and `|y| <= |a / g|`.
+///
+/// # Panics
+///
+/// Neither input may be the minimum value of `T`: `gcd(i64::MIN, 0)` is not
+/// representable, and the steps overflow for such inputs, which panics in
+/// debug builds. Widen them to a larger type first.
+///
+/// # Examples
+///
+/// ```
+/// use algorithm_rs::math::number_theory::ext_gcd;
+///
+/// let (g, x, y) = ext_gcd(240i64, -46);
+/// assert_eq!(g, 2);
+/// assert_eq!(240 * x - 46 * y, 2);
+/// ```
+pub fn ext_gcd<T>(a: T, b: T) -> (T, T, T)
+where
+    T: Copy
+        + PartialOrd
+        + From<i8>
+        + Sub<Output = T>
+        + Mul<Output = T>
+        + Div<Output = T>
+        + Rem<Output = T>
+        + Neg<Output = T>,
+{
+    let zero = T::from(0);
+    let (mut r0, mut r1) = (a, b);
+    let (mut x0, mut x1) = (T::from(1), zero);
+    let (mut y0, mut y1) = (zero, T::from(1));
+    while r1 != zero {
+        let q = r0 / r1;
+        (r0, r1) = (r1, r0 - q * r1);
+        (x0, x1) = (x1, x0 - q * x1);
+        (y0, y1) = (y1, y0 - q * y1);
+    }
+
+    if r0 < zero {
+        (-r0, -x0, -y0)
+    } else {
+        (r0, x0, y0)
+    }
+}
+
+/// Returns the inverse of `a` modulo `m` in `0..m`, or `None` if `a` and
+/// `m` are not coprime or `m <= 0`.
+///
+/// # Examples
+///
+/// ```
+/// use algorithm_rs::math::number_theory::inv_mod;
+///
+/// assert_eq!(inv_mod(3, 7), Some(5));
+/// assert_eq!(inv_mod(-3, 7), Some(2));
+/// assert_eq!(inv_mod(4, 6), None);
+/// ```
+pub fn inv_mod(a: i64, m: i64) -> Option<i64> {
+    if m <= 0 {
+        return None;
+    }
+
+    let (g, x, _) = ext_gcd(a.rem_euclid(m) as i128, m as i128);
+    (g == 1).then(|| x.rem_euclid(m as i128) as i64)
+}
+
+/// Solves the system `x = r_i (mod m_i)`, returning `(x, lcm)` with
+/// `0 <= x < lcm`.
+///
+/// The moduli need not be coprime. Returns `None` if the congruences are
+/// inconsistent, a modulus is not positive, or the lcm overflows `i64`.
+/// An empty system gives `(0, 1)`.
+///
+/// # Examples
+///
+/// ```
+/// use algorithm_rs::math::number_theory::crt;
+///
+/// assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+/// assert_eq!(crt(&[(1, 4), (3, 6)]), Some((9, 12)));
+/// assert_eq!(crt(&[(1, 4), (2, 6)]), None);
+/// ```
+pub fn crt(congruences: &[(i64, i64)]) -> Option<(i64, i64)> {
+    let (mut r0, mut m0) = (0i128, 1i128);
+    for &(r, m) in congruences {
+        if m <= 0 {
+            return None;
+        }
+        let (r1, m1) = ((r as i128).rem_euclid(m as i128), m as i128);
+
+        // r0 + m0 t = r1 (mod m1)  <=>  (m0 / g) t = (r1 - r0) / g (mod m1 / g)
+        let (g, p, _) = ext_gcd(m0, m1);
+        if (r1 - r0) % g != 0 {
+            return None;
+        }
+        let u = m1 / g;
+        let t = ((r1 - r0) / g % u * p).rem_euclid(u);
+        r0 += m0 * t;
+        m0 *= u;
+        if m0 > i64::MAX as i128 {
+            return None;
+        }
+    }
+
+    Some((r0 as i64, m0 as i64))
+}
+
+/// Returns `x mod target`, where `x` is the unique value in
+/// `0..prod(moduli)` with `x = residues[i] (mod moduli[i])`.
+///
+/// The product of the moduli may be far beyond `u64`; Garner's algorithm
+/// only ever works modulo the individual moduli and `target`. Returns
+/// `None` if the moduli are not pairwise coprime or a modulus is zero.
+///
+/// # Panics
+///
+/// Panics if the slices have different lengths or `target` is zero.
+///
+/// # Examples
+///
+/// ```
+/// use algorithm_rs::math::number_theory::garner;
+///
+/// // x = 10^20 + 7 taken modulo three primes.
+/// let moduli = [998_244_353, 1_000_000_007, 1_000_000_009];
+/// let x: u128 = 100_000_000_000_000_000_007;
+/// let residues: Vec<u64> = moduli.iter().map(|&m| (x % m as u128) as u64).collect();
+/// assert_eq!(garner(&residues, &moduli, u64::MAX), Some((x % u64::MAX as u128) as u64));
+/// ```
+pub fn garner(residues: &[u64], moduli: &[u64], target: u64) -> Option<u64> {
+    assert_eq!(
+        residues.len(),
+        moduli.len(),
+        "residue and modulus counts differ"
+    );
+    assert!(target > 0, "target modulus must be positive");
+    if moduli.contains(&0) {
+        return None;
+    }
+
+    let mul = |a: u64, b: u64, m: u64| (a as u128 * b as u128 % m as u128) as u64;
+
+    // x = c_0 + c_1 m_0 + c_2 m_0 m_1 + ...; prefix[j] tracks the partial
+    // sum modulo moduli[j] (and modulo target at the end), power[j] the
+    // product of the moduli so far.
+    let n = moduli.len();
+    let mut mods = moduli.to_vec();
+    mods.push(target);
+    let mut prefix = vec![0u64; n + 1];
+    let mut power: Vec<u64> = mods.iter().map(|&m| 1 % m).collect();
+    for i in 0..n {
+        let m = mods[i];
+        let diff = ((residues[i] % m) as u128 + (m - prefix[i]) as u128) % m as u128;
+        let (g, inv, _) = ext_gcd(power[i] as i128, m as i128);
+        if g != 1 {
+            return None;
+        }
+        let c = mul(diff as u64, inv.rem_euclid(m as i128) as u64, m);
+        for j in i + 1..=n {
+            let sum = prefix[j] as u128 + mul(c, power[j], mods[j]) as u128;
+            prefix[j] = (sum % mods[j] as u128) as u64;
+            power[j] = mul(power[j], m, mods[j]);
+        }
+    }
+
+    Some(prefix[n])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::Rng;
+
+    fn gcd(a: i64, b: i64) -> i64 {
+        if b == 0 {
+            a.abs()
+        } else {
+            gcd(b, a % b)
+        }
+    }
+
+    #[test]
+    fn test_ext_gcd() {
+        let mut rng = Rng(50);
+        for _ in 0..1000 {
+            let a = rng.next() as i64 >> 2;
+            let b = rng.next() as i64 >> (rng.next() % 60);
+            let (g, x, y) = ext_gcd(a, b);
+            assert_eq!(g, gcd(a, b));
+            assert_eq!(a as i128 * x as i128 + b as i128 * y as i128, g as i128);
+        }
+        assert_eq!(ext_gcd(0i32, 0), (0, 1, 0));
+        assert_eq!(ext_gcd(0i8, -5), (5, 0, -1));
+        assert_eq!(ext_gcd(i128::MAX, 3).0, 1);
+        assert_eq!(ext_gcd(i8::MIN + 1, i8::MAX), (127, 0, 1));
+        assert_eq!(ext_gcd(i8::MIN as i16, 0), (128, -1, 0));
+    }
+
+    #[test]
+    fn test_inv_mod() {
+        for m in 1..200i64 {
+            for a in -200..200 {
+                let expected = (0..m).find(|&x| (a * x).rem_euclid(m) == 1 % m);
+                assert_eq!(inv_mod(a, m), expected, "{} mod {}", a, m);
+            }
+        }
+        assert_eq!(inv_mod(2, i64::MAX), Some(i64::MAX / 2 + 1));
+        assert_eq!(inv_mod(1, 0), None);
+    }
+
+    #[test]
+    fn test_crt() {
+        for m1 in 1..20i64 {
+            for m2 in 1..20 {
+                for r1 in -3..m1 {
+                    for r2 in 0..m2 {
+                        let l = m1 / gcd(m1, m2) * m2;
+                        let expected = (0..l)
+                            .find(|&x| (x - r1).rem_euclid(m1) == 0 && (x - r2) % m2 == 0)
+                            .map(|x| (x, l));
+                        assert_eq!(crt(&[(r1, m1), (r2, m2)]), expected);
+                    }
+                }
+            }
+        }
+
+        let big = 2_000_000_011;
+        assert_eq!(
+            crt(&[(1, big), (2, big + 2)]).map(|(x, _)| x % big),
+            Some(1)
+        );
+        assert_eq!(crt(&[(1, i64::MAX), (0, 2)]), None);
+        assert_eq!(crt(&[]), Some((0, 1)));
+    }
+
+    #[test]
+    fn test_garner() {
+        let mut rng = Rng(51);
+        let moduli = [
+            998_244_353u64,
+            1_000_000_007,
+            754_974_721,
+            9_223_372_036_854_775_783,
+        ];
+        for _ in 0..200 {
+            let x = (rng.next() as u128) << 64 | rng.next() as u128;
+            let residues: Vec<u64> = moduli.iter().map(|&m| (x % m as u128) as u64).collect();
+            for target in [1u64, 2, 1_000_000_007, u64::MAX] {
+                // The product of the moduli exceeds 2^128, so x is the solution.
+                let expected = (x % target as u128) as u64;
+                assert_eq!(garner(&residues, &moduli, target), Some(expected));
+            }
+        }
+
+        assert_eq!(garner(&[1, 1], &[4, 6], 100), None);
+        assert_eq!(garner(&[], &[], 7), Some(0));
+    }
+}
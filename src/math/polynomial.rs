@@ -0,0 +1,345 @@
+//! Polynomial operations on [`FormalPowerSeries`]: division, Taylor shifts,
+//! multipoint evaluation and interpolation.
+//!
+//! Here a series is read as a polynomial, so trailing zero coefficients are
+//! insignificant and results come back without them.
+
+use crate::math::combinatorics::Binomial;
+use crate::math::convolution::convolution;
+use crate::math::fps::FormalPowerSeries;
+use crate::math::modint::ModIntLike;
+use std::ops::{Div, Rem};
+
+/// Divisions with a side at most this long use schoolbook long division.
+const NAIVE_THRESHOLD: usize = 64;
+
+impl<T: ModIntLike> FormalPowerSeries<T> {
+    /// Returns the degree, or `None` for the zero polynomial.
+    pub fn degree(&self) -> Option<usize> {
+        self.iter().rposition(|&x| x != T::zero())
+    }
+
+    /// Evaluates the polynomial at `x`.
+    pub fn eval(&self, x: T) -> T {
+        self.iter().rev().fold(T::zero(), |acc, &c| acc * x + c)
+    }
+
+    /// Returns `self(x + c)` in `O(n log n)`.
+    ///
+    /// The modulus must be a prime larger than the degree.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use algorithm_rs::math::fps::FormalPowerSeries;
+    /// use algorithm_rs::math::modint::ModInt998244353 as Mint;
+    ///
+    /// // (x + 1)^2 = x^2 + 2x + 1
+    /// let f: FormalPowerSeries<Mint> = [0, 0, 1].iter().map(|&x| Mint::new(x)).collect();
+    /// let g: Vec<u32> = f.taylor_shift(Mint::new(1)).iter().map(|x| x.val()).collect();
+    /// assert_eq!(g, vec![1, 2, 1]);
+    /// ```
+    pub fn taylor_shift(&self, c: T) -> Self {
+        let n = self.len();
+        if n == 0 {
+            return Self::default();
+        }
+
+        // g_k k! = sum_i f_i i! c^(i-k) / (i-k)!, a correlation of two sequences.
+        let mut binom = Binomial::with_capacity(n);
+        let a: Vec<T> = (0..n).rev().map(|i| self[i] * binom.fact(i)).collect();
+        let mut power = T::one();
+        let b: Vec<T> = (0..n)
+            .map(|j| {
+                let term = power * binom.inv_fact(j);
+                power *= c;
+                term
+            })
+            .collect();
+
+        let ab = convolution(&a, &b);
+        (0..n).map(|k| ab[n - 1 - k] * binom.inv_fact(k)).collect()
+    }
+
+    /// Returns the quotient and remainder of dividing by `rhs`.
+    ///
+    /// The quotient is computed from the reversed polynomials as
+    /// `rev(self) / rev(rhs)` modulo `x^(deg self - deg rhs + 1)`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rhs` is zero or its leading coefficient is not invertible.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use algorithm_rs::math::fps::FormalPowerSeries;
+    /// use algorithm_rs::math::modint::ModInt998244353 as Mint;
+    ///
+    /// // x^3 + 2x + 5 = (x^2 + x + 3)(x - 1) + 8
+    /// let f: FormalPowerSeries<Mint> = [5, 2, 0, 1].iter().map(|&x| Mint::new(x)).collect();
+    /// let g: FormalPowerSeries<Mint> = [-1, 1].iter().map(|&x| Mint::new(x)).collect();
+    /// let (q, r) = f.div_rem(&g);
+    /// assert_eq!(q.iter().map(|x| x.val()).collect::<Vec<_>>(), vec![3, 1, 1]);
+    /// assert_eq!(r.iter().map(|x| x.val()).collect::<Vec<_>>(), vec![8]);
+    /// ```
+    pub fn div_rem(&self, rhs: &Self) -> (Self, Self) {
+        let m = rhs.degree().expect("division by the zero polynomial");
+        let n = match self.degree() {
+            Some(n) if n >= m => n,
+            _ => {
+                let mut r = self.clone();
+                r.shrink();
+                return (Self::default(), r);
+            }
+        };
+
+        let k = n - m + 1;
+        let mut q = if k.min(m) <= NAIVE_THRESHOLD {
+            self.div_naive(rhs, n, m)
+        } else {
+            let f: Self = self[..=n].iter().rev().copied().collect();
+            let g: Self = rhs[..=m].iter().rev().copied().collect();
+            let q = (&f.truncate(k) * &g.inv(k)).truncate(k);
+            q.iter().rev().copied().collect()
+        };
+        let mut r = (self.truncate(m) - (&q.truncate(k.min(m)) * &rhs.truncate(m))).truncate(m);
+        q.shrink();
+        r.shrink();
+
+        (q, r)
+    }
+
+    fn div_naive(&self, rhs: &Self, n: usize, m: usize) -> Self {
+        let inv = rhs[m]
+            .checked_inv()
+            .expect("leading coefficient must be invertible");
+        let mut r = self[..=n].to_vec();
+        let mut q = vec![T::zero(); n - m + 1];
+        for i in (0..q.len()).rev() {
+            let c = r[i + m] * inv;
+            q[i] = c;
+            for (x, &y) in r[i..=i + m].iter_mut().zip(&rhs[..=m]) {
+                *x -= c * y;
+            }
+        }
+
+        Self::new(q)
+    }
+
+    /// Evaluates the polynomial at every point of `xs` in `O(n log^2 n)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use algorithm_rs::math::fps::FormalPowerSeries;
+    /// use algorithm_rs::math::modint::ModInt998244353 as Mint;
+    ///
+    /// let f: FormalPowerSeries<Mint> = [1, 0, 1].iter().map(|&x| Mint::new(x)).collect();
+    /// let xs: Vec<Mint> = [0, 1, 2, 3].iter().map(|&x| Mint::new(x)).collect();
+    /// let ys: Vec<u32> = f.multipoint_eval(&xs).iter().map(|y| y.val()).collect();
+    /// assert_eq!(ys, vec![1, 2, 5, 10]);
+    /// ```
+    pub fn multipoint_eval(&self, xs: &[T]) -> Vec<T> {
+        if xs.is_empty() {
+            return Vec::new();
+        }
+
+        let tree = SubproductTree::new(xs);
+        let mut ys = vec![T::zero(); xs.len()];
+        tree.evaluate(1, &(self % &tree.nodes[1]), xs, &mut ys);
+        ys
+    }
+
+    /// Returns the unique polynomial of degree below `n` through the `n`
+    /// points `(xs[i], ys[i])`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the lengths differ or the `xs` are not distinct.
+    pub fn interpolate(xs: &[T], ys: &[T]) -> Self {
+        assert_eq!(xs.len(), ys.len(), "point and value counts differ");
+        if xs.is_empty() {
+            return Self::default();
+        }
+
+        // Lagrange: f = sum ys[i] / g'(xs[i]) * g / (x - xs[i]) with g = prod (x - xs[i]).
+        let tree = SubproductTree::new(xs);
+        let weights = tree.nodes[1].derivative().multipoint_eval(xs);
+        let mut nodes = vec![Self::default(); 2 * tree.size];
+        for (i, (&y, &w)) in ys.iter().zip(&weights).enumerate() {
+            let w = w
+                .checked_inv()
+                .expect("interpolation points must be distinct");
+            nodes[tree.size + i] = Self::new(vec![y * w]);
+        }
+        for i in (1..tree.size).rev() {
+            let l = &nodes[2 * i] * &tree.nodes[2 * i + 1];
+            let r = &nodes[2 * i + 1] * &tree.nodes[2 * i];
+            nodes[i] = l + r;
+        }
+
+        let mut f = std::mem::take(&mut nodes[1]);
+        f.shrink();
+        f
+    }
+}
+
+/// The products of `x - xs[i]` over the leaves below each node of a
+/// complete binary tree; padding leaves hold the constant one.
+struct SubproductTree<T> {
+    size: usize,
+    nodes: Vec<FormalPowerSeries<T>>,
+}
+
+impl<T: ModIntLike> SubproductTree<T> {
+    fn new(xs: &[T]) -> Self {
+        let size = xs.len().next_power_of_two();
+        let mut nodes = vec![FormalPowerSeries::new(vec![T::one()]); 2 * size];
+        for (node, &x) in nodes[size..].iter_mut().zip(xs) {
+            *node = FormalPowerSeries::new(vec![-x, T::one()]);
+        }
+        for i in (1..size).rev() {
+            nodes[i] = &nodes[2 * i] * &nodes[2 * i + 1];
+        }
+
+        Self { size, nodes }
+    }
+
+    /// Fills `ys` for the points below `node`, given `f` reduced modulo it.
+    fn evaluate(&self, node: usize, f: &FormalPowerSeries<T>, xs: &[T], ys: &mut [T]) {
+        let lo = node << (self.size.trailing_zeros() - node.ilog2());
+        if lo - self.size >= xs.len() {
+            return;
+        }
+        if f.len() <= NAIVE_THRESHOLD {
+            let hi = (lo + (self.size >> node.ilog2())).min(self.size + xs.len());
+            for i in lo - self.size..hi - self.size {
+                ys[i] = f.eval(xs[i]);
+            }
+            return;
+        }
+
+        self.evaluate(2 * node, &(f % &self.nodes[2 * node]), xs, ys);
+        self.evaluate(2 * node + 1, &(f % &self.nodes[2 * node + 1]), xs, ys);
+    }
+}
+
+impl<T: ModIntLike> Div for &FormalPowerSeries<T> {
+    type Output = FormalPowerSeries<T>;
+
+    fn div(self, rhs: Self) -> FormalPowerSeries<T> {
+        self.div_rem(rhs).0
+    }
+}
+
+impl<T: ModIntLike> Rem for &FormalPowerSeries<T> {
+    type Output = FormalPowerSeries<T>;
+
+    fn rem(self, rhs: Self) -> FormalPowerSeries<T> {
+        self.div_rem(rhs).1
+    }
+}
+
+impl<T: ModIntLike> Div for FormalPowerSeries<T> {
+    type Output = Self;
+
+    fn div(self, rhs: Self) -> Self {
+        self.div_rem(&rhs).0
+    }
+}
+
+impl<T: ModIntLike> Rem for FormalPowerSeries<T> {
+    type Output = Self;
+
+    fn rem(self, rhs: Self) -> Self {
+        self.div_rem(&rhs).1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::modint::{ModInt1000000007, ModInt998244353};
+    use crate::test_util::Rng;
+
+    fn random<T: ModIntLike>(rng: &mut Rng, n: usize) -> FormalPowerSeries<T> {
+        (0..n).map(|_| T::new(rng.next())).collect()
+    }
+
+    fn check_div_rem<T: ModIntLike>(rng: &mut Rng) {
+        for &(n, m) in &[
+            (1, 1),
+            (5, 9),
+            (10, 3),
+            (300, 200),
+            (1000, 100),
+            (1000, 900),
+        ] {
+            let f: FormalPowerSeries<T> = random(rng, n);
+            let mut g: FormalPowerSeries<T> = random(rng, m);
+            g[m - 1] = T::one();
+
+            let (q, r) = f.div_rem(&g);
+            assert!(r.degree() < g.degree());
+            let mut back = &q * &g + r;
+            back.shrink();
+            let mut f = f;
+            f.shrink();
+            assert_eq!(back, f);
+        }
+    }
+
+    #[test]
+    fn test_div_rem() {
+        let mut rng = Rng(20);
+        check_div_rem::<ModInt998244353>(&mut rng);
+        check_div_rem::<ModInt1000000007>(&mut rng);
+
+        let f = FormalPowerSeries::new(vec![ModInt998244353::new(3), 0.into(), 0.into()]);
+        let g = FormalPowerSeries::new(vec![ModInt998244353::new(1), 1.into()]);
+        assert_eq!(&f / &g, FormalPowerSeries::default());
+        assert_eq!(f % g, FormalPowerSeries::new(vec![3.into()]));
+    }
+
+    #[test]
+    fn test_taylor_shift() {
+        let mut rng = Rng(23);
+        for n in [0, 1, 5, 200] {
+            let f: FormalPowerSeries<ModInt998244353> = random(&mut rng, n);
+            let c = ModInt998244353::new(rng.next());
+            let g = f.taylor_shift(c);
+            assert_eq!(g.len(), n);
+            for x in [0u32, 1, 12345] {
+                let x = ModInt998244353::new(x);
+                assert_eq!(g.eval(x), f.eval(x + c));
+            }
+        }
+    }
+
+    #[test]
+    fn test_multipoint_eval() {
+        let mut rng = Rng(21);
+        for &(n, m) in &[(1, 1), (3, 100), (100, 3), (500, 700), (2000, 1000)] {
+            let f: FormalPowerSeries<ModInt998244353> = random(&mut rng, n);
+            let xs: Vec<ModInt998244353> =
+                (0..m).map(|_| ModInt998244353::new(rng.next())).collect();
+            let expected: Vec<_> = xs.iter().map(|&x| f.eval(x)).collect();
+            assert_eq!(f.multipoint_eval(&xs), expected);
+        }
+    }
+
+    #[test]
+    fn test_interpolate() {
+        let mut rng = Rng(22);
+        for n in [1, 2, 50, 300, 1000] {
+            let mut f: FormalPowerSeries<ModInt1000000007> = random(&mut rng, n);
+            f.shrink();
+            let xs: Vec<ModInt1000000007> = (0..n as u32)
+                .map(|i| ModInt1000000007::new(i * 7 + 3))
+                .collect();
+            let ys = f.multipoint_eval(&xs);
+            assert_eq!(FormalPowerSeries::interpolate(&xs, &ys), f);
+        }
+    }
+}
@@ -0,0 +1,458 @@
+//! Primality testing, factorization and the functions built on it, for `u64`.
+//!
+//! Moduli below `2^63` run on [`Montgomery64`]; larger ones fall back to
+//! `u128` multiplication.
+
+use crate::math::montgomery::Montgomery64;
+
+/// Modular arithmetic in some internal representation of residues.
+trait Arith {
+    fn to(&self, a: u64) -> u64;
+    fn one(&self) -> u64;
+    fn add(&self, a: u64, b: u64) -> u64;
+    fn mul(&self, a: u64, b: u64) -> u64;
+    fn pow(&self, a: u64, exp: u64) -> u64;
+}
+
+impl Arith for Montgomery64 {
+    fn to(&self, a: u64) -> u64 {
+        self.to_mont(a)
+    }
+
+    fn one(&self) -> u64 {
+        Montgomery64::one(self)
+    }
+
+    fn add(&self, a: u64, b: u64) -> u64 {
+        Montgomery64::add(self, a, b)
+    }
+
+    fn mul(&self, a: u64, b: u64) -> u64 {
+        Montgomery64::mul(self, a, b)
+    }
+
+    fn pow(&self, a: u64, exp: u64) -> u64 {
+        Montgomery64::pow(self, a, exp)
+    }
+}
+
+/// Plain residues with `u128` products, for moduli of `2^63` and above.
+struct Wide(u64);
+
+impl Arith for Wide {
+    fn to(&self, a: u64) -> u64 {
+        a % self.0
+    }
+
+    fn one(&self) -> u64 {
+        1 % self.0
+    }
+
+    fn add(&self, a: u64, b: u64) -> u64 {
+        ((a as u128 + b as u128) % self.0 as u128) as u64
+    }
+
+    fn mul(&self, a: u64, b: u64) -> u64 {
+        (a as u128 * b as u128 % self.0 as u128) as u64
+    }
+
+    fn pow(&self, mut a: u64, mut exp: u64) -> u64 {
+        let mut result = self.one();
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result = self.mul(result, a);
+            }
+            a = self.mul(a, a);
+            exp >>= 1;
+        }
+        result
+    }
+}
+
+const SMALL_PRIMES: [u64; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+
+/// Returns `true` if `n` is prime.
+///
+/// Uses Miller–Rabin with the seven bases of Jim Sinclair, which is
+/// deterministic for all `u64`.
+///
+/// # Examples
+///
+/// ```
+/// use algorithm_rs::math::prime::is_prime;
+///
+/// assert!(is_prime(998_244_353));
+/// assert!(!is_prime(3_215_031_751)); // a strong pseudoprime to bases 2, 3, 5, 7
+/// assert!(is_prime(18_446_744_073_709_551_557)); // the largest u64 prime
+/// ```
+pub fn is_prime(n: u64) -> bool {
+    if n < 2 {
+        return false;
+    }
+    for p in SMALL_PRIMES {
+        if n.is_multiple_of(p) {
+            return n == p;
+        }
+    }
+    if n < 41 * 41 {
+        return true;
+    }
+
+    if n < 1 << 63 {
+        miller_rabin(&Montgomery64::new(n), n)
+    } else {
+        miller_rabin(&Wide(n), n)
+    }
+}
+
+fn miller_rabin<A: Arith>(m: &A, n: u64) -> bool {
+    let s = (n - 1).trailing_zeros();
+    let d = (n - 1) >> s;
+    let one = m.one();
+    let minus_one = m.to(n - 1);
+    [2, 325, 9375, 28178, 450775, 9780504, 1795265022]
+        .iter()
+        .all(|&a| {
+            let a = a % n;
+            if a == 0 {
+                return true;
+            }
+            let mut x = m.pow(m.to(a), d);
+            if x == one || x == minus_one {
+                return true;
+            }
+            for _ in 1..s {
+                x = m.mul(x, x);
+                if x == minus_one {
+                    return true;
+                }
+            }
+            false
+        })
+}
+
+fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// Returns a nontrivial factor of the odd composite `n`.
+fn find_factor(n: u64) -> u64 {
+    if n < 1 << 63 {
+        pollard_brent(&Montgomery64::new(n), n)
+    } else {
+        pollard_brent(&Wide(n), n)
+    }
+}
+
+/// Brent's variant of Pollard's rho, batching the gcds.
+fn pollard_brent<A: Arith>(m: &A, n: u64) -> u64 {
+    const BATCH: u64 = 128;
+
+    for c in 1.. {
+        let c = m.to(c);
+        let f = |x: u64| m.add(m.mul(x, x), c);
+        let (mut x, mut y, mut ys) = (0, m.to(2), 0);
+        let mut q = m.one();
+        let mut g = 1;
+        let mut r = 1;
+        while g == 1 {
+            x = y;
+            for _ in 0..r {
+                y = f(y);
+            }
+            let mut k = 0;
+            while k < r && g == 1 {
+                ys = y;
+                for _ in 0..BATCH.min(r - k) {
+                    y = f(y);
+                    q = m.mul(q, x.abs_diff(y));
+                }
+                // Montgomery form preserves gcds with n, since 2^64 is a unit.
+                g = gcd(q, n);
+                k += BATCH;
+            }
+            r *= 2;
+        }
+
+        if g == n {
+            // The batch overshot; retrace it one step at a time.
+            loop {
+                ys = f(ys);
+                g = gcd(x.abs_diff(ys), n);
+                if g != 1 {
+                    break;
+                }
+            }
+        }
+        if g != n {
+            return g;
+        }
+    }
+    unreachable!()
+}
+
+fn factorize_into(n: u64, primes: &mut Vec<u64>) {
+    if n == 1 {
+        return;
+    }
+    if is_prime(n) {
+        primes.push(n);
+        return;
+    }
+    let d = find_factor(n);
+    factorize_into(d, primes);
+    factorize_into(n / d, primes);
+}
+
+/// Returns the prime factorization of `n` as sorted `(prime, exponent)` pairs.
+///
+/// Small factors are removed by trial division and the rest are split with
+/// Brent's Pollard rho, in about `O(n^(1/4))` per factor.
+///
+/// # Examples
+///
+/// ```
+/// use algorithm_rs::math::prime::factorize;
+///
+/// assert_eq!(factorize(360), vec![(2, 3), (3, 2), (5, 1)]);
+/// assert_eq!(
+///     factorize(999_999_999_989 * 1_000_003),
+///     vec![(1_000_003, 1), (999_999_999_989, 1)]
+/// );
+/// assert!(factorize(1).is_empty());
+/// ```
+///
+/// # Panics
+///
+/// Panics if `n` is zero.
+pub fn factorize(mut n: u64) -> Vec<(u64, u32)> {
+    assert!(n > 0, "cannot factorize zero");
+    let mut primes = Vec::new();
+    for p in SMALL_PRIMES {
+        while n.is_multiple_of(p) {
+            primes.push(p);
+            n /= p;
+        }
+    }
+    factorize_into(n, &mut primes);
+    primes.sort_unstable();
+
+    let mut factors: Vec<(u64, u32)> = Vec::new();
+    for p in primes {
+        match factors.last_mut() {
+            Some((q, e)) if *q == p => *e += 1,
+            _ => factors.push((p, 1)),
+        }
+    }
+
+    factors
+}
+
+/// Returns the divisors of `n >= 1` in increasing order.
+pub fn divisors(n: u64) -> Vec<u64> {
+    let mut divisors = vec![1];
+    for (p, e) in factorize(n) {
+        let len = divisors.len();
+        let mut power = 1;
+        for _ in 0..e {
+            power *= p;
+            for i in 0..len {
+                divisors.push(divisors[i] * power);
+            }
+        }
+    }
+    divisors.sort_unstable();
+
+    divisors
+}
+
+/// Returns Euler's totient from a factorization.
+fn phi_of(factors: &[(u64, u32)]) -> u64 {
+    factors
+        .iter()
+        .map(|&(p, e)| p.pow(e - 1) * (p - 1))
+        .product()
+}
+
+/// Returns the smallest primitive root modulo `n`, if one exists.
+///
+/// Primitive roots exist exactly for `1, 2, 4, p^k` and `2 p^k` with `p` an
+/// odd prime.
+///
+/// # Examples
+///
+/// ```
+/// use algorithm_rs::math::prime::primitive_root;
+///
+/// assert_eq!(primitive_root(998_244_353), Some(3));
+/// assert_eq!(primitive_root(50), Some(3));
+/// assert_eq!(primitive_root(8), None);
+/// ```
+pub fn primitive_root(n: u64) -> Option<u64> {
+    match n {
+        0 => return None,
+        1 | 2 => return Some(n - 1),
+        4 => return Some(3),
+        _ => {}
+    }
+
+    let factors = factorize(n);
+    let odd = factors.iter().filter(|&&(p, _)| p != 2).count();
+    let twos = factors
+        .iter()
+        .find(|&&(p, _)| p == 2)
+        .map_or(0, |&(_, e)| e);
+    if odd != 1 || twos > 1 {
+        return None;
+    }
+
+    let phi = phi_of(&factors);
+    let phi_primes: Vec<u64> = factorize(phi).into_iter().map(|(q, _)| q).collect();
+    let pow = |a: u64, e: u64| {
+        let mut result = 1u128;
+        let mut base = a as u128;
+        let mut e = e;
+        while e > 0 {
+            if e & 1 == 1 {
+                result = result * base % n as u128;
+            }
+            base = base * base % n as u128;
+            e >>= 1;
+        }
+        result as u64
+    };
+
+    (2..n).find(|&g| gcd(g, n) == 1 && phi_primes.iter().all(|&q| pow(g, phi / q) != 1))
+}
+
+/// Returns the Carmichael function `lambda(n)`, the exponent of the
+/// multiplicative group modulo `n >= 1`.
+///
+/// # Examples
+///
+/// ```
+/// use algorithm_rs::math::prime::carmichael_lambda;
+///
+/// assert_eq!(carmichael_lambda(8), 2);
+/// assert_eq!(carmichael_lambda(15), 4);
+/// assert_eq!(carmichael_lambda(561), 80);
+/// ```
+pub fn carmichael_lambda(n: u64) -> u64 {
+    factorize(n)
+        .into_iter()
+        .map(|(p, e)| match (p, e) {
+            (2, 1) => 1,
+            (2, 2) => 2,
+            (2, _) => 1 << (e - 2),
+            _ => p.pow(e - 1) * (p - 1),
+        })
+        .fold(1, |l, x| l / gcd(l, x) * x)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::Rng;
+
+    #[test]
+    fn test_is_prime_small() {
+        let n = 100_000;
+        let mut composite = vec![false; n];
+        for i in 2..n {
+            if !composite[i] {
+                for j in (2 * i..n).step_by(i) {
+                    composite[j] = true;
+                }
+            }
+            assert_eq!(is_prime(i as u64), !composite[i], "{}", i);
+        }
+        assert!(!is_prime(0) && !is_prime(1));
+    }
+
+    #[test]
+    fn test_is_prime_large() {
+        let primes = [
+            1_000_000_007,
+            4_294_967_291,
+            1_000_000_000_000_000_003,
+            9_223_372_036_854_775_783,
+            18_446_744_073_709_551_557,
+        ];
+        let composites = [
+            // Strong pseudoprimes to many small bases.
+            3_215_031_751,
+            341_550_071_728_321,
+            3_825_123_056_546_413_051,
+            561,
+            4_294_967_291 * 4_294_967_279,
+            9_223_372_036_854_775_783 * 2,
+            18_446_744_073_709_551_557 - 2,
+        ];
+        assert!(primes.iter().all(|&p| is_prime(p)));
+        assert!(composites.iter().all(|&c| !is_prime(c)));
+    }
+
+    #[test]
+    fn test_factorize() {
+        let mut rng = Rng(40);
+        let mut cases = vec![
+            1,
+            2,
+            4_294_967_291 * 4_294_967_279,
+            1_000_000_007 * 998_244_353,
+            u64::MAX,
+            1 << 63,
+            9_223_372_036_854_775_783,
+            18_446_744_073_709_551_557,
+        ];
+        cases.extend((0..200).map(|_| rng.next()));
+        cases.extend((0..200).map(|_| rng.next() >> 20));
+
+        for n in cases {
+            let factors = factorize(n);
+            let mut product = 1u64;
+            for w in factors.windows(2) {
+                assert!(w[0].0 < w[1].0);
+            }
+            for &(p, e) in &factors {
+                assert!(is_prime(p), "{} is not prime", p);
+                product *= p.pow(e);
+            }
+            assert_eq!(product, n);
+        }
+    }
+
+    #[test]
+    fn test_divisors_and_roots() {
+        for n in 1..=300u64 {
+            let expected: Vec<u64> = (1..=n).filter(|&d| n.is_multiple_of(d)).collect();
+            assert_eq!(divisors(n), expected);
+
+            // Brute-force multiplicative orders.
+            let units: Vec<u64> = (1..=n).filter(|&a| gcd(a, n) == 1).collect();
+            let order = |a: u64| {
+                let mut x = a % n;
+                let mut k = 1;
+                while x != 1 % n {
+                    x = x * a % n;
+                    k += 1;
+                }
+                k
+            };
+            let lambda = units.iter().map(|&a| order(a)).max().unwrap();
+            assert_eq!(carmichael_lambda(n), lambda);
+
+            let root = units
+                .iter()
+                .copied()
+                .find(|&a| order(a) == units.len() as u64);
+            assert_eq!(
+                primitive_root(n),
+                root.map(|g| if n <= 2 { n - 1 } else { g })
+            );
+        }
+    }
+}
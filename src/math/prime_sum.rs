@@ -0,0 +1,384 @@
+//! Sums over primes up to `n` and prefix sums of multiplicative functions.
+//!
+//! Both rely on the fact that `floor(n / i)` takes only `O(sqrt(n))` distinct
+//! values. Lucy_Hedgehog's method tabulates sums over primes at all of them
+//! in `O(n^(3/4) / log n)`, and the Min_25 sieve builds on those tables to sum
+//! any multiplicative function whose values at primes are a polynomial.
+//! `n` up to about `10^11` is practical.
+
+use crate::math::combinatorics::Binomial;
+use crate::math::modint::ModIntLike;
+use crate::math::sieve::LinearSieve;
+use std::ops::{Mul, Sub};
+
+/// Sums of a completely multiplicative `g` over the primes up to `v`, for
+/// every `v = floor(n / i)`.
+///
+/// # Examples
+///
+/// ```
+/// use algorithm_rs::math::prime_sum::PrimeSums;
+///
+/// // Sum of squares of the primes.
+/// let sums = PrimeSums::new(100, |p| p * p, |v| (v * (v + 1) * (2 * v + 1) / 6).saturating_sub(1));
+/// assert_eq!(sums.get(10), 4 + 9 + 25 + 49);
+/// assert_eq!(sums.get(100 / 3), 4 + 9 + 25 + 49 + 121 + 169 + 289 + 361 + 529 + 841 + 961);
+/// ```
+#[derive(Clone, Debug)]
+pub struct PrimeSums<T> {
+    n: u64,
+    sqrt: u64,
+    small: Vec<T>,
+    large: Vec<T>,
+}
+
+impl<T> PrimeSums<T>
+where
+    T: Copy + Sub<Output = T> + Mul<Output = T>,
+{
+    /// Sieves the sums for `g`, where `prefix(v)` must return
+    /// `g(2) + g(3) + ... + g(v)` (zero for `v < 2`).
+    pub fn new<G, P>(n: u64, g: G, prefix: P) -> Self
+    where
+        G: Fn(u64) -> T,
+        P: Fn(u64) -> T,
+    {
+        let sqrt = n.isqrt();
+        let mut small: Vec<T> = (0..=sqrt).map(&prefix).collect();
+        // large[i] holds the sum up to n / i; index 0 is unused.
+        let mut large: Vec<T> = (0..=sqrt)
+            .map(|i| prefix(n.checked_div(i).unwrap_or(0)))
+            .collect();
+
+        // Sieving by p removes the numbers whose smallest prime factor is p.
+        for &p in LinearSieve::new(sqrt as usize).primes() {
+            let p = p as u64;
+            let gp = g(p);
+            let base = small[p as usize - 1];
+            let limit = sqrt.min(n / (p * p));
+            for i in 1..=limit {
+                let d = i * p;
+                let other = if d <= sqrt {
+                    large[d as usize]
+                } else {
+                    small[(n / d) as usize]
+                };
+                large[i as usize] = large[i as usize] - gp * (other - base);
+            }
+            for v in (p * p..=sqrt).rev() {
+                let v = v as usize;
+                small[v] = small[v] - gp * (small[v / p as usize] - base);
+            }
+        }
+
+        Self {
+            n,
+            sqrt,
+            small,
+            large,
+        }
+    }
+
+    /// Returns the limit the sums were sieved for.
+    pub fn n(&self) -> u64 {
+        self.n
+    }
+
+    /// Returns the sum of `g(p)` over the primes `p <= v`.
+    ///
+    /// `v` must be `floor(n / i)` for some `i >= 1`.
+    pub fn get(&self, v: u64) -> T {
+        if v <= self.sqrt {
+            self.small[v as usize]
+        } else {
+            self.large[(self.n / v) as usize]
+        }
+    }
+}
+
+/// Returns the number of primes up to `n`.
+///
+/// # Examples
+///
+/// ```
+/// use algorithm_rs::math::prime_sum::prime_count;
+///
+/// assert_eq!(prime_count(100), 25);
+/// assert_eq!(prime_count(1_000_000_000), 50_847_534);
+/// ```
+pub fn prime_count(n: u64) -> u64 {
+    PrimeSums::new(n, |_| 1, |v| v.saturating_sub(1)).get(n)
+}
+
+/// Returns the sum of the primes up to `n`.
+///
+/// # Examples
+///
+/// ```
+/// use algorithm_rs::math::prime_sum::prime_sum;
+///
+/// assert_eq!(prime_sum(100), 1060);
+/// ```
+pub fn prime_sum(n: u64) -> u128 {
+    PrimeSums::new(
+        n,
+        |p| p as u128,
+        |v| {
+            let v = v as u128;
+            (v * (v + 1) / 2).saturating_sub(1)
+        },
+    )
+    .get(n)
+}
+
+/// Returns `f(1) + f(2) + ... + f(n)` for a multiplicative `f` given by
+/// `f(p^k) = prime_power(p, k)`.
+///
+/// At primes `f` must agree with the polynomial `prime_poly[0] + prime_poly[1]
+/// p + prime_poly[2] p^2 + ...`, which is what lets the sum over primes come
+/// from [`PrimeSums`]. The modulus must exceed the polynomial's degree plus
+/// one.
+///
+/// # Examples
+///
+/// ```
+/// use algorithm_rs::math::modint::ModInt998244353 as Mint;
+/// use algorithm_rs::math::prime_sum::multiplicative_sum;
+///
+/// // Euler's totient: phi(p) = p - 1 and phi(p^k) = p^(k-1) (p - 1).
+/// let poly = [Mint::new(-1), Mint::new(1)];
+/// let sum = multiplicative_sum(10, &poly, |p, k| Mint::new(p).pow(k as u64 - 1) * Mint::new(p - 1));
+/// assert_eq!(sum, Mint::new(32));
+/// ```
+pub fn multiplicative_sum<T, F>(n: u64, prime_poly: &[T], mut prime_power: F) -> T
+where
+    T: ModIntLike,
+    F: FnMut(u64, u32) -> T,
+{
+    if n == 0 {
+        return T::zero();
+    }
+
+    let degree = prime_poly.len();
+    let mut binom = Binomial::with_capacity(degree + 1);
+    let inv_fact: Vec<T> = (0..=degree).map(|i| binom.inv_fact(i)).collect();
+    let tables: Vec<PrimeSums<T>> = (0..degree)
+        .map(|j| {
+            // 1^j + ... + v^j is a polynomial of degree j + 1 in v.
+            let mut ys = vec![T::zero(); j + 2];
+            for x in 1..=j + 1 {
+                ys[x] = ys[x - 1] + T::from(x).pow(j as u64);
+            }
+            PrimeSums::new(
+                n,
+                |p| T::new(p).pow(j as u64),
+                |v| interpolate_at(&ys, &inv_fact, v.max(1)) - T::one(),
+            )
+        })
+        .collect();
+
+    let primes: Vec<u64> = LinearSieve::new(n.isqrt() as usize)
+        .primes()
+        .iter()
+        .map(|&p| p as u64)
+        .collect();
+    let ctx = MinCtx {
+        prime_poly,
+        tables: &tables,
+        primes: &primes,
+    };
+
+    ctx.sum(n, 0, &mut prime_power) + T::one()
+}
+
+/// Shared state of the Min_25 recursion.
+struct MinCtx<'a, T> {
+    prime_poly: &'a [T],
+    tables: &'a [PrimeSums<T>],
+    primes: &'a [u64],
+}
+
+impl<T: ModIntLike> MinCtx<'_, T> {
+    /// Returns `f` summed over the primes up to `v`.
+    fn prime_total(&self, v: u64) -> T {
+        self.prime_poly
+            .iter()
+            .zip(self.tables)
+            .map(|(&c, table)| c * table.get(v))
+            .sum()
+    }
+
+    /// Returns `f(x)` summed over `2 <= x <= v` whose smallest prime factor
+    /// is at least `primes[j]`.
+    fn sum<F: FnMut(u64, u32) -> T>(&self, v: u64, j: usize, prime_power: &mut F) -> T {
+        let below = if j == 0 {
+            T::zero()
+        } else {
+            self.prime_total(self.primes[j - 1])
+        };
+        let mut result = self.prime_total(v) - below;
+
+        for (k, &p) in self.primes.iter().enumerate().skip(j) {
+            if p * p > v {
+                break;
+            }
+            let mut pe = p;
+            let mut e = 1;
+            while pe * p <= v {
+                result += prime_power(p, e) * self.sum(v / pe, k + 1, prime_power)
+                    + prime_power(p, e + 1);
+                pe *= p;
+                e += 1;
+            }
+        }
+
+        result
+    }
+}
+
+/// Evaluates at `v` the polynomial of degree `< ys.len()` taking the values
+/// `ys` at `0, 1, ...`, by Lagrange interpolation.
+fn interpolate_at<T: ModIntLike>(ys: &[T], inv_fact: &[T], v: u64) -> T {
+    let d = ys.len() - 1;
+    if v <= d as u64 {
+        return ys[v as usize];
+    }
+
+    // prod_{k != i} (v - k) from prefix and suffix products.
+    let x = T::new(v);
+    let mut suffix = vec![T::one(); d + 2];
+    for i in (0..=d).rev() {
+        suffix[i] = suffix[i + 1] * (x - T::from(i));
+    }
+    let mut prefix = T::one();
+    let mut result = T::zero();
+    for (i, &y) in ys.iter().enumerate() {
+        let term = y * prefix * suffix[i + 1] * inv_fact[i] * inv_fact[d - i];
+        if (d - i).is_multiple_of(2) {
+            result += term;
+        } else {
+            result -= term;
+        }
+        prefix *= x - T::from(i);
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::modint::ModInt998244353;
+    use crate::test_util::Rng;
+
+    type Mint = ModInt998244353;
+
+    #[test]
+    fn test_prime_count_and_sum() {
+        let limit = 1_000_000;
+        let sieve = LinearSieve::new(limit);
+        let mut count = vec![0u64; limit + 1];
+        let mut sum = vec![0u128; limit + 1];
+        for x in 1..=limit {
+            let prime = sieve.is_prime(x);
+            count[x] = count[x - 1] + prime as u64;
+            sum[x] = sum[x - 1] + if prime { x as u128 } else { 0 };
+        }
+
+        let mut rng = Rng(70);
+        let ns = (0..300u64).chain((0..100).map(|_| rng.next() % limit as u64));
+        for n in ns {
+            assert_eq!(prime_count(n), count[n as usize], "pi({})", n);
+            assert_eq!(prime_sum(n), sum[n as usize], "sum({})", n);
+        }
+
+        assert_eq!(prime_count(10_000_000_000), 455_052_511);
+        assert_eq!(prime_sum(1_000_000_000), 24_739_512_092_254_535);
+    }
+
+    #[test]
+    fn test_prime_sums_at_quotients() {
+        let n = 123_456;
+        let sieve = LinearSieve::new(n);
+        let sums = PrimeSums::new(
+            n as u64,
+            |p| Mint::new(p).pow(3),
+            |v| (2..=v).map(|x| Mint::new(x).pow(3)).sum(),
+        );
+        let mut expected = vec![Mint::zero(); n + 1];
+        for x in 1..=n {
+            let cube = Mint::new(x).pow(3);
+            expected[x] = expected[x - 1]
+                + if sieve.is_prime(x) {
+                    cube
+                } else {
+                    Mint::zero()
+                };
+        }
+        for i in 1..=n {
+            let v = n / i;
+            assert_eq!(sums.get(v as u64), expected[v], "v = {}", v);
+        }
+    }
+
+    #[test]
+    fn test_multiplicative_sum() {
+        let limit = 20_000;
+        let sieve = LinearSieve::new(limit);
+        let phi = sieve.phi();
+        let mu = sieve.mobius();
+        let d = sieve.divisor_count();
+        let sigma = sieve.divisor_sum();
+
+        let prefix = |f: &dyn Fn(usize) -> Mint, n: usize| (1..=n).map(f).sum::<Mint>();
+        for n in (0..200).chain([1_000, 4_096, 9_999, limit]) {
+            let got = multiplicative_sum(n as u64, &[Mint::new(-1), Mint::new(1)], |p, k| {
+                Mint::new(p).pow(k as u64 - 1) * Mint::new(p - 1)
+            });
+            assert_eq!(got, prefix(&|x| Mint::new(phi[x]), n), "phi {}", n);
+
+            let got = multiplicative_sum(n as u64, &[Mint::new(-1)], |_, k| {
+                Mint::new(if k == 1 { -1 } else { 0 })
+            });
+            assert_eq!(got, prefix(&|x| Mint::new(mu[x]), n), "mu {}", n);
+
+            let got = multiplicative_sum(n as u64, &[Mint::new(2)], |_, k| Mint::new(k + 1));
+            assert_eq!(got, prefix(&|x| Mint::new(d[x]), n), "d {}", n);
+
+            let got = multiplicative_sum(n as u64, &[Mint::new(1), Mint::new(1)], |p, k| {
+                (0..=k).map(|i| Mint::new(p).pow(i as u64)).sum()
+            });
+            assert_eq!(got, prefix(&|x| Mint::new(sigma[x]), n), "sigma {}", n);
+        }
+
+        // Far beyond the sieve, against the Dirichlet hyperbola recursion.
+        let n = 100_000_000u64;
+        let got = multiplicative_sum(n, &[Mint::new(-1), Mint::new(1)], |p, k| {
+            Mint::new(p).pow(k as u64 - 1) * Mint::new(p - 1)
+        });
+        assert_eq!(got, totient_sum(n));
+    }
+
+    /// Returns sum_{i <= n} phi(i) from sum_{d} Phi(n / d) = n (n + 1) / 2.
+    fn totient_sum(n: u64) -> Mint {
+        use std::collections::HashMap;
+
+        fn go(n: u64, memo: &mut HashMap<u64, Mint>) -> Mint {
+            if let Some(&v) = memo.get(&n) {
+                return v;
+            }
+            let mut result = Mint::new(n as u128 * (n as u128 + 1) / 2);
+            let mut d = 2;
+            while d <= n {
+                let q = n / d;
+                let next = n / q;
+                result -= Mint::new(next - d + 1) * go(q, memo);
+                d = next + 1;
+            }
+            memo.insert(n, result);
+            result
+        }
+
+        go(n, &mut HashMap::new())
+    }
+}
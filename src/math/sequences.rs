@@ -0,0 +1,215 @@
+//! Classical counting sequences computed with formal power series.
+//!
+//! Every function returns the first terms of a sequence (or a full row) in
+//! `O(n log n)` and needs a prime modulus larger than `n`.
+
+use crate::math::combinatorics::Binomial;
+use crate::math::convolution::convolution;
+use crate::math::fps::FormalPowerSeries;
+use crate::math::modint::ModIntLike;
+
+/// Returns the Stirling numbers of the second kind `S(n, 0..=n)`.
+///
+/// Uses `S(n, k) = sum_i (-1)^(k-i) i^n / (i! (k-i)!)`.
+///
+/// # Examples
+///
+/// ```
+/// use algorithm_rs::math::modint::ModInt998244353 as Mint;
+/// use algorithm_rs::math::sequences::stirling2_row;
+///
+/// let row: Vec<u32> = stirling2_row::<Mint>(4).iter().map(|x| x.val()).collect();
+/// assert_eq!(row, vec![0, 1, 7, 6, 1]);
+/// ```
+pub fn stirling2_row<T: ModIntLike>(n: usize) -> Vec<T> {
+    let mut binom = Binomial::with_capacity(n);
+    let a: Vec<T> = (0..=n)
+        .map(|i| T::from(i).pow(n as u64) * binom.inv_fact(i))
+        .collect();
+    let b: Vec<T> = (0..=n)
+        .map(|i| {
+            let x = binom.inv_fact(i);
+            if i % 2 == 0 {
+                x
+            } else {
+                -x
+            }
+        })
+        .collect();
+
+    let mut row = convolution(&a, &b);
+    row.truncate(n + 1);
+    row
+}
+
+/// Returns the unsigned Stirling numbers of the first kind `[n, 0..=n]`.
+///
+/// They are the coefficients of the rising factorial `x (x+1) ... (x+n-1)`,
+/// built by doubling with [`FormalPowerSeries::taylor_shift`]. The signed
+/// numbers are `s(n, k) = (-1)^(n-k) [n, k]`.
+///
+/// # Examples
+///
+/// ```
+/// use algorithm_rs::math::modint::ModInt998244353 as Mint;
+/// use algorithm_rs::math::sequences::stirling1_row;
+///
+/// let row: Vec<u32> = stirling1_row::<Mint>(4).iter().map(|x| x.val()).collect();
+/// assert_eq!(row, vec![0, 6, 11, 6, 1]);
+/// ```
+pub fn stirling1_row<T: ModIntLike>(n: usize) -> Vec<T> {
+    // f holds x (x+1) ... (x+m-1).
+    let mut f = FormalPowerSeries::new(vec![T::one()]);
+    let mut m = 0usize;
+    for bit in (0..usize::BITS - n.leading_zeros()).rev() {
+        if m > 0 {
+            f = &f * &f.taylor_shift(T::from(m));
+            m *= 2;
+        }
+        if n >> bit & 1 == 1 {
+            f = &f * &FormalPowerSeries::new(vec![T::from(m), T::one()]);
+            m += 1;
+        }
+    }
+
+    f.truncate(n + 1).into_vec()
+}
+
+/// Returns the Bell numbers `B_0..=B_n`, from `exp(e^x - 1)`.
+pub fn bell<T: ModIntLike>(n: usize) -> Vec<T> {
+    let mut binom = Binomial::with_capacity(n);
+    let f: FormalPowerSeries<T> = (0..=n)
+        .map(|i| if i == 0 { T::zero() } else { binom.inv_fact(i) })
+        .collect();
+
+    f.exp(n + 1)
+        .iter()
+        .enumerate()
+        .map(|(i, &x)| x * binom.fact(i))
+        .collect()
+}
+
+/// Returns the Bernoulli numbers `B_0..=B_n`, from `x / (e^x - 1)`.
+///
+/// This is the convention with `B_1 = -1/2`.
+///
+/// # Examples
+///
+/// ```
+/// use algorithm_rs::math::modint::ModInt998244353 as Mint;
+/// use algorithm_rs::math::sequences::bernoulli;
+///
+/// let b = bernoulli::<Mint>(4);
+/// assert_eq!(b[1] * Mint::new(-2), Mint::new(1));
+/// assert_eq!(b[4] * Mint::new(-30), Mint::new(1));
+/// ```
+pub fn bernoulli<T: ModIntLike>(n: usize) -> Vec<T> {
+    let mut binom = Binomial::with_capacity(n + 1);
+    let f: FormalPowerSeries<T> = (0..=n).map(|i| binom.inv_fact(i + 1)).collect();
+
+    f.inv(n + 1)
+        .iter()
+        .enumerate()
+        .map(|(i, &x)| x * binom.fact(i))
+        .collect()
+}
+
+/// Returns the partition numbers `p(0..=n)`.
+///
+/// The generating function is the inverse of Euler's pentagonal series
+/// `prod (1 - x^k) = sum_k (-1)^k x^(k (3k - 1) / 2)`.
+///
+/// # Examples
+///
+/// ```
+/// use algorithm_rs::math::modint::ModInt998244353 as Mint;
+/// use algorithm_rs::math::sequences::partitions;
+///
+/// let p: Vec<u32> = partitions::<Mint>(7).iter().map(|x| x.val()).collect();
+/// assert_eq!(p, vec![1, 1, 2, 3, 5, 7, 11, 15]);
+/// ```
+pub fn partitions<T: ModIntLike>(n: usize) -> Vec<T> {
+    let mut euler = FormalPowerSeries::zeros(n + 1);
+    euler[0] = T::one();
+    for k in 1.. {
+        let sign = if k % 2 == 0 { T::one() } else { -T::one() };
+        let a = k * (3 * k - 1) / 2;
+        if a > n {
+            break;
+        }
+        euler[a] += sign;
+        if a + k <= n {
+            euler[a + k] += sign;
+        }
+    }
+
+    euler.inv(n + 1).into_vec()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::modint::{ModInt1000000007, ModInt998244353};
+
+    type Mint = ModInt998244353;
+
+    const N: usize = 150;
+
+    fn stirling_tables<T: ModIntLike>() -> (Vec<Vec<T>>, Vec<Vec<T>>) {
+        let mut s1 = vec![vec![T::zero(); N + 1]; N + 1];
+        let mut s2 = s1.clone();
+        s1[0][0] = T::one();
+        s2[0][0] = T::one();
+        for n in 1..=N {
+            for k in 1..=n {
+                s1[n][k] = s1[n - 1][k - 1] + T::from(n - 1) * s1[n - 1][k];
+                s2[n][k] = s2[n - 1][k - 1] + T::from(k) * s2[n - 1][k];
+            }
+        }
+        (s1, s2)
+    }
+
+    fn check_stirling<T: ModIntLike>() {
+        let (s1, s2) = stirling_tables::<T>();
+        for n in [0, 1, 2, 3, 7, 64, 100, N] {
+            assert_eq!(stirling1_row::<T>(n), s1[n][..=n]);
+            assert_eq!(stirling2_row::<T>(n), s2[n][..=n]);
+        }
+    }
+
+    #[test]
+    fn test_stirling() {
+        check_stirling::<Mint>();
+        check_stirling::<ModInt1000000007>();
+    }
+
+    #[test]
+    fn test_bell_and_bernoulli() {
+        let (_, s2) = stirling_tables::<Mint>();
+        let expected: Vec<Mint> = s2.iter().map(|row| row.iter().copied().sum()).collect();
+        assert_eq!(bell::<Mint>(N), expected);
+
+        // sum_{k<=n} C(n+1, k) B_k = 0 for n >= 1.
+        let b = bernoulli::<Mint>(N);
+        let mut binom = Binomial::<Mint>::new();
+        assert_eq!(b[0], Mint::one());
+        for n in 1..N {
+            let sum: Mint = (0..=n).map(|k| binom.c(n + 1, k) * b[k]).sum();
+            assert_eq!(sum, Mint::zero());
+        }
+    }
+
+    #[test]
+    fn test_partitions() {
+        let mut p = vec![Mint::zero(); N + 1];
+        p[0] = Mint::one();
+        for k in 1..=N {
+            for i in k..=N {
+                let add = p[i - k];
+                p[i] += add;
+            }
+        }
+        assert_eq!(partitions::<Mint>(N), p);
+        assert_eq!(partitions::<Mint>(0), vec![Mint::one()]);
+    }
+}
@@ -0,0 +1,386 @@
+//! Prime sieves: a linear sieve with multiplicative functions and a
+//! segmented sieve for ranges far beyond memory.
+
+use std::ops::Mul;
+
+/// Smallest prime factors and primes up to a limit, computed in `O(n)`.
+///
+/// # Examples
+///
+/// ```
+/// use algorithm_rs::math::sieve::LinearSieve;
+///
+/// let sieve = LinearSieve::new(360);
+/// assert_eq!(sieve.primes()[..10], [2, 3, 5, 7, 11, 13, 17, 19, 23, 29]);
+/// assert_eq!(sieve.factorize(360), vec![(2, 3), (3, 2), (5, 1)]);
+/// assert_eq!(sieve.phi()[12], 4);
+/// ```
+#[derive(Clone, Debug)]
+pub struct LinearSieve {
+    spf: Vec<u32>,
+    primes: Vec<u32>,
+}
+
+impl LinearSieve {
+    /// Sieves `0..=n`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` does not fit in `u32`.
+    pub fn new(n: usize) -> Self {
+        assert!(n <= u32::MAX as usize, "sieve limit must fit in u32");
+        let mut spf = vec![0u32; n + 1];
+        let mut primes = Vec::new();
+        for i in 2..=n {
+            if spf[i] == 0 {
+                spf[i] = i as u32;
+                primes.push(i as u32);
+            }
+            // Each composite is crossed out once, by its smallest prime factor.
+            for &p in &primes {
+                let j = i * p as usize;
+                if p > spf[i] || j > n {
+                    break;
+                }
+                spf[j] = p;
+            }
+        }
+
+        Self { spf, primes }
+    }
+
+    /// Returns the largest sieved number.
+    pub fn limit(&self) -> usize {
+        self.spf.len() - 1
+    }
+
+    /// Returns the primes in increasing order.
+    pub fn primes(&self) -> &[u32] {
+        &self.primes
+    }
+
+    /// Returns `true` if `x` is prime.
+    pub fn is_prime(&self, x: usize) -> bool {
+        x >= 2 && self.spf[x] == x as u32
+    }
+
+    /// Returns the smallest prime factor of `x >= 2`.
+    pub fn smallest_prime_factor(&self, x: usize) -> u32 {
+        self.spf[x]
+    }
+
+    /// Returns the prime factorization of `x >= 1` as `(prime, exponent)` pairs.
+    pub fn factorize(&self, mut x: usize) -> Vec<(u32, u32)> {
+        let mut factors: Vec<(u32, u32)> = Vec::new();
+        while x > 1 {
+            let p = self.spf[x];
+            match factors.last_mut() {
+                Some((q, e)) if *q == p => *e += 1,
+                _ => factors.push((p, 1)),
+            }
+            x /= p as usize;
+        }
+
+        factors
+    }
+
+    /// Tabulates the multiplicative function with `f(p^k) = prime_power(p, k)`
+    /// over `0..=n`; the entry at `0` is meaningless and `f(1) = 1`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use algorithm_rs::math::sieve::LinearSieve;
+    ///
+    /// // The number of squarefree divisors is 2^omega(n).
+    /// let sieve = LinearSieve::new(12);
+    /// let f = sieve.multiplicative(|_, _| 2u32);
+    /// assert_eq!(f[12], 4);
+    /// ```
+    pub fn multiplicative<T, F>(&self, mut prime_power: F) -> Vec<T>
+    where
+        T: Copy + Mul<Output = T> + From<u8>,
+        F: FnMut(u32, u32) -> T,
+    {
+        let n = self.limit();
+        let mut f = vec![T::from(1); n + 1];
+        // The full power of the smallest prime dividing x, and its exponent.
+        let mut power = vec![1u32; n + 1];
+        let mut exp = vec![0u8; n + 1];
+        for x in 2..=n {
+            let p = self.spf[x];
+            let y = x / p as usize;
+            if self.spf[y] == p {
+                power[x] = power[y] * p;
+                exp[x] = exp[y] + 1;
+            } else {
+                power[x] = p;
+                exp[x] = 1;
+            }
+
+            f[x] = if power[x] as usize == x {
+                prime_power(p, exp[x] as u32)
+            } else {
+                f[power[x] as usize] * f[x / power[x] as usize]
+            };
+        }
+
+        f
+    }
+
+    /// Tabulates Euler's totient.
+    pub fn phi(&self) -> Vec<u32> {
+        self.multiplicative(|p, k| p.pow(k - 1) * (p - 1))
+    }
+
+    /// Tabulates the Möbius function.
+    pub fn mobius(&self) -> Vec<i32> {
+        self.multiplicative(|_, k| if k == 1 { -1 } else { 0 })
+    }
+
+    /// Tabulates the number of divisors.
+    pub fn divisor_count(&self) -> Vec<u32> {
+        self.multiplicative(|_, k| k + 1)
+    }
+
+    /// Tabulates the sum of divisors.
+    pub fn divisor_sum(&self) -> Vec<u64> {
+        self.multiplicative(|p, k| (0..=k).map(|i| (p as u64).pow(i)).sum())
+    }
+}
+
+/// Numbers sieved per block by [`SegmentedPrimes`].
+const BLOCK: u64 = 1 << 16;
+
+/// The largest upper end accepted by [`SegmentedPrimes`].
+const MAX_HI: u64 = 1 << 50;
+
+/// Returns the primes up to `n` from a bit sieve over the odd numbers.
+fn small_primes(n: u64) -> Vec<u32> {
+    if n < 2 {
+        return Vec::new();
+    }
+    // Bit i stands for 2i + 1.
+    let len = (n as usize - 1) / 2 + 1;
+    let mut composite = vec![0u64; len.div_ceil(64)];
+    let mut i = 1;
+    while (2 * i + 1) * (2 * i + 1) <= n as usize {
+        if composite[i / 64] >> (i % 64) & 1 == 0 {
+            let p = 2 * i + 1;
+            for j in (p * p / 2..len).step_by(p) {
+                composite[j / 64] |= 1 << (j % 64);
+            }
+        }
+        i += 1;
+    }
+
+    let odd = (1..len).filter(|&i| composite[i / 64] >> (i % 64) & 1 == 0);
+    std::iter::once(2)
+        .chain(odd.map(|i| (2 * i + 1) as u32))
+        .collect()
+}
+
+/// Iterator over the primes in `[lo, hi]`, sieving one fixed-size block at a
+/// time.
+///
+/// The base primes up to `sqrt(hi)` come from a bit sieve, so memory is
+/// about `sqrt(hi) / 16` bytes plus four per base prime and the block,
+/// regardless of the range length: about 10 MB at the limit `hi = 2^50`.
+/// `hi` up to about `10^14` keeps the time per block practical.
+///
+/// # Examples
+///
+/// ```
+/// use algorithm_rs::math::sieve::SegmentedPrimes;
+///
+/// let primes: Vec<u64> = SegmentedPrimes::new(1_000_000_000_000, 1_000_000_000_100).collect();
+/// assert_eq!(primes, vec![1_000_000_000_039, 1_000_000_000_061, 1_000_000_000_063, 1_000_000_000_091]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct SegmentedPrimes {
+    base: Vec<u32>,
+    /// The start of the next block, or `None` past `u64::MAX`.
+    next: Option<u64>,
+    hi: u64,
+    found: Vec<u64>,
+    pos: usize,
+}
+
+impl SegmentedPrimes {
+    /// Prepares to enumerate the primes in `[lo, hi]`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `hi` exceeds `2^50`.
+    pub fn new(lo: u64, hi: u64) -> Self {
+        assert!(hi <= MAX_HI, "segmented sieve limit {} exceeds 2^50", hi);
+        let base = small_primes(hi.isqrt());
+        Self {
+            base,
+            next: Some(lo.max(2)),
+            hi,
+            found: Vec::new(),
+            pos: 0,
+        }
+    }
+
+    /// Sieves the next block into `found`; returns `false` once past `hi`.
+    fn sieve_block(&mut self) -> bool {
+        let Some(start) = self.next.filter(|&next| next <= self.hi) else {
+            return false;
+        };
+        let end = self.hi.min(start.saturating_add(BLOCK - 1));
+        self.next = end.checked_add(1);
+
+        let mut composite = vec![false; (end - start + 1) as usize];
+        for &p in &self.base {
+            let p = p as u64;
+            if p * p > end {
+                break;
+            }
+            let Some(first) = start.div_ceil(p).checked_mul(p) else {
+                continue;
+            };
+            for m in ((p * p).max(first)..=end).step_by(p as usize) {
+                composite[(m - start) as usize] = true;
+            }
+        }
+
+        self.found.clear();
+        self.pos = 0;
+        self.found.extend(
+            composite
+                .iter()
+                .enumerate()
+                .filter(|&(_, &c)| !c)
+                .map(|(i, _)| start + i as u64),
+        );
+        true
+    }
+}
+
+impl Iterator for SegmentedPrimes {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<u64> {
+        while self.pos == self.found.len() {
+            if !self.sieve_block() {
+                return None;
+            }
+        }
+        self.pos += 1;
+        Some(self.found[self.pos - 1])
+    }
+}
+
+/// Returns the primes in `[lo, hi]`; see [`SegmentedPrimes`].
+///
+/// # Panics
+///
+/// Panics if `hi` exceeds `2^50`.
+pub fn segmented_primes(lo: u64, hi: u64) -> Vec<u64> {
+    SegmentedPrimes::new(lo, hi).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::prime::is_prime;
+
+    fn gcd(a: u64, b: u64) -> u64 {
+        if b == 0 {
+            a
+        } else {
+            gcd(b, a % b)
+        }
+    }
+
+    #[test]
+    fn test_primes_and_factorize() {
+        let sieve = LinearSieve::new(10_000);
+        let expected: Vec<u32> = (2..=10_000u32)
+            .filter(|&n| (2..n).take_while(|d| d * d <= n).all(|d| n % d != 0))
+            .collect();
+        assert_eq!(sieve.primes(), &expected[..]);
+        assert!(!sieve.is_prime(0) && !sieve.is_prime(1) && sieve.is_prime(9973));
+
+        for x in 1..=10_000 {
+            let product: usize = sieve
+                .factorize(x)
+                .iter()
+                .map(|&(p, e)| (p as usize).pow(e))
+                .product();
+            assert_eq!(product, x);
+        }
+    }
+
+    #[test]
+    fn test_multiplicative_functions() {
+        let n = 2000;
+        let sieve = LinearSieve::new(n);
+        let phi = sieve.phi();
+        let mu = sieve.mobius();
+        let d = sieve.divisor_count();
+        let sigma = sieve.divisor_sum();
+
+        for x in 1..=n {
+            let divisors: Vec<u64> = (1..=x as u64)
+                .filter(|&i| (x as u64).is_multiple_of(i))
+                .collect();
+            let coprime = (1..=x as u64).filter(|&i| gcd(i, x as u64) == 1).count();
+            assert_eq!(phi[x] as usize, coprime);
+            assert_eq!(d[x] as usize, divisors.len());
+            assert_eq!(sigma[x], divisors.iter().sum::<u64>());
+
+            // sum_{d | x} mu(d) = [x = 1]
+            let mu_sum: i32 = divisors.iter().map(|&i| mu[i as usize]).sum();
+            assert_eq!(mu_sum, (x == 1) as i32);
+        }
+    }
+
+    #[test]
+    fn test_segmented() {
+        let sieve = LinearSieve::new(300_000);
+        for &(lo, hi) in &[
+            (0, 0),
+            (0, 2),
+            (2, 2),
+            (4, 4),
+            (1, 300_000),
+            (65_000, 200_000),
+        ] {
+            let expected: Vec<u64> = sieve
+                .primes()
+                .iter()
+                .map(|&p| p as u64)
+                .filter(|&p| lo <= p && p <= hi)
+                .collect();
+            assert_eq!(segmented_primes(lo, hi), expected);
+        }
+
+        let base = LinearSieve::new(1_000_010);
+        let (lo, hi) = (999_999_999_800, 1_000_000_000_100);
+        let expected: Vec<u64> = (lo..=hi)
+            .filter(|&n| base.primes().iter().all(|&p| n % p as u64 != 0))
+            .collect();
+        assert_eq!(segmented_primes(lo, hi), expected);
+    }
+
+    #[test]
+    fn test_segmented_near_limit() {
+        for n in 0..100 {
+            let expected: Vec<u32> = LinearSieve::new(n).primes;
+            assert_eq!(small_primes(n as u64), expected);
+        }
+
+        let (lo, hi) = (MAX_HI - 2 * BLOCK, MAX_HI);
+        let expected: Vec<u64> = (lo..=hi).filter(|&n| is_prime(n)).collect();
+        assert_eq!(segmented_primes(lo, hi), expected);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_segmented_at_u64_max() {
+        SegmentedPrimes::new(u64::MAX - 100, u64::MAX);
+    }
+}
@@ -0,0 +1,1141 @@
+//! Arbitrary-precision signed integers.
+//!
+//! A [`BigInt`] is a sign and a little-endian magnitude of 32-bit limbs
+//! without leading zeros. Multiplication switches from schoolbook to
+//! Karatsuba to a three-prime NTT as the operands grow, and division uses
+//! Knuth's algorithm D or, for long divisors, a Newton reciprocal, so that
+//! the divide-and-conquer decimal conversions run in `O(M(n) log n)`.
+
+use crate::math::convolution::convolution_u64;
+use crate::num::integer::Integer;
+use std::cmp::Ordering;
+use std::fmt::{self, Debug, Display};
+use std::iter::{Product, Sum};
+use std::ops::{
+    Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, RemAssign, Sub, SubAssign,
+};
+use std::str::FromStr;
+
+/// Limbs below which the shorter factor is multiplied by schoolbook.
+const KARATSUBA_THRESHOLD: usize = 32;
+
+/// Limbs from which the shorter factor is multiplied by NTT.
+const NTT_THRESHOLD: usize = 512;
+
+/// Limbs in both factors together above which the 16-bit digit product
+/// would exceed the `2^24` terms one exact convolution can produce, so
+/// Karatsuba splits them first.
+const NTT_MAX_LIMBS: usize = 1 << 23;
+
+/// Limbs from which divisor and quotient use Newton division.
+const NEWTON_THRESHOLD: usize = 128;
+
+/// Decimal digits per base-`10^9` chunk in string conversions.
+const CHUNK_DIGITS: usize = 9;
+
+const CHUNK: u32 = 1_000_000_000;
+
+/// Base-`10^9` chunks converted by short division or Horner's rule.
+const CHUNK_LEAF: usize = 16;
+
+/// An arbitrary-precision signed integer.
+///
+/// Division and remainder truncate toward zero like the primitive integer
+/// types, so the remainder takes the sign of the dividend.
+///
+/// # Examples
+///
+/// ```
+/// use algorithm_rs::num::BigInt;
+///
+/// let a: BigInt = "123456789012345678901234567890".parse().unwrap();
+/// let b = BigInt::from(-987_654_321i64);
+/// assert_eq!((&a * &b).to_string(), "-121932631124828532112482853211126352690");
+/// assert_eq!((&a / &b).to_string(), "-124999998873437499901");
+/// assert_eq!((&a % &b).to_string(), "574845669");
+/// assert_eq!(BigInt::from(2).pow(100).to_string(), "1267650600228229401496703205376");
+/// ```
+#[derive(Clone, Default, PartialEq, Eq, Hash)]
+pub struct BigInt {
+    negative: bool,
+    mag: Vec<u32>,
+}
+
+impl BigInt {
+    /// Builds a value from a sign and a magnitude that may have leading zeros.
+    fn from_parts(negative: bool, mut mag: Vec<u32>) -> Self {
+        trim(&mut mag);
+        Self {
+            negative: negative && !mag.is_empty(),
+            mag,
+        }
+    }
+
+    /// Returns zero.
+    pub fn zero() -> Self {
+        Self::default()
+    }
+
+    /// Returns one.
+    pub fn one() -> Self {
+        Self::from(1)
+    }
+
+    /// Returns `true` if the value is zero.
+    pub fn is_zero(&self) -> bool {
+        self.mag.is_empty()
+    }
+
+    /// Returns `true` if the value is less than zero.
+    pub fn is_negative(&self) -> bool {
+        self.negative
+    }
+
+    /// Returns `-1`, `0` or `1` according to the sign.
+    pub fn signum(&self) -> i32 {
+        if self.negative {
+            -1
+        } else {
+            !self.is_zero() as i32
+        }
+    }
+
+    /// Returns the absolute value.
+    pub fn abs(&self) -> Self {
+        Self {
+            negative: false,
+            mag: self.mag.clone(),
+        }
+    }
+
+    /// Returns the number of bits in the magnitude; zero has none.
+    pub fn bits(&self) -> u64 {
+        match self.mag.last() {
+            Some(&top) => 32 * self.mag.len() as u64 - top.leading_zeros() as u64,
+            None => 0,
+        }
+    }
+
+    /// Returns the nearest `f64`, or an infinity if the value is out of range.
+    pub fn to_f64(&self) -> f64 {
+        let low = |mag: &[u32]| {
+            mag.iter()
+                .take(4)
+                .rev()
+                .fold(0u128, |acc, &d| acc << 32 | d as u128)
+        };
+        let bits = self.bits();
+        let magnitude = if bits <= 128 {
+            low(&self.mag) as f64
+        } else {
+            // The top 64 bits and a sticky bit for the rest round correctly.
+            let shift = bits - 64;
+            let (limbs, rest) = ((shift / 32) as usize, (shift % 32) as u32);
+            let top = low(&shr_bits(&self.mag[limbs..], rest)) as u64;
+            let sticky = self.mag[..limbs].iter().any(|&d| d != 0)
+                || self.mag[limbs] & ((1u64 << rest) - 1) as u32 != 0;
+            let scale = 2f64.powi((shift - 1).min(4096) as i32);
+            ((top as u128) << 1 | sticky as u128) as f64 * scale
+        };
+        if self.negative {
+            -magnitude
+        } else {
+            magnitude
+        }
+    }
+
+    /// Returns `self` raised to the power `exp`, with `0^0 = 1`.
+    pub fn pow(&self, mut exp: u32) -> Self {
+        let mut result = Self::one();
+        let mut base = self.clone();
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result *= &base;
+            }
+            exp >>= 1;
+            if exp > 0 {
+                base = &base * &base;
+            }
+        }
+        result
+    }
+
+    /// Returns the truncated quotient and the remainder together.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rhs` is zero.
+    pub fn div_rem(&self, rhs: &Self) -> (Self, Self) {
+        assert!(!rhs.is_zero(), "attempt to divide by zero");
+        let (q, r) = divmod_mag(&self.mag, &rhs.mag);
+        (
+            Self::from_parts(self.negative != rhs.negative, q),
+            Self::from_parts(self.negative, r),
+        )
+    }
+
+    /// Returns the floored quotient and the non-negative remainder, like
+    /// [`i64::div_euclid`] and [`i64::rem_euclid`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rhs` is zero.
+    pub fn div_rem_euclid(&self, rhs: &Self) -> (Self, Self) {
+        let (mut q, mut r) = self.div_rem(rhs);
+        if r.negative {
+            if rhs.negative {
+                q += &Self::one();
+                r -= rhs;
+            } else {
+                q -= &Self::one();
+                r += rhs;
+            }
+        }
+        (q, r)
+    }
+
+    /// Returns the greatest common divisor, which is never negative.
+    pub fn gcd(&self, other: &Self) -> Self {
+        let (mut a, mut b) = (self.abs(), other.abs());
+        while !b.is_zero() {
+            let r = &a % &b;
+            a = std::mem::replace(&mut b, r);
+        }
+        a
+    }
+
+    /// Adds `rhs` with its sign flipped when `negate` is set.
+    fn add_signed(&self, rhs: &Self, negate: bool) -> Self {
+        let rhs_negative = rhs.negative != negate;
+        if self.negative == rhs_negative {
+            return Self::from_parts(self.negative, add_mag(&self.mag, &rhs.mag));
+        }
+        match cmp_mag(&self.mag, &rhs.mag) {
+            Ordering::Less => Self::from_parts(rhs_negative, sub_mag(&rhs.mag, &self.mag)),
+            _ => Self::from_parts(self.negative, sub_mag(&self.mag, &rhs.mag)),
+        }
+    }
+}
+
+/// Drops leading zero limbs.
+fn trim(x: &mut Vec<u32>) {
+    while x.last() == Some(&0) {
+        x.pop();
+    }
+}
+
+/// Returns `x` without leading zero limbs.
+fn trimmed(x: &[u32]) -> &[u32] {
+    let len = x.iter().rposition(|&d| d != 0).map_or(0, |i| i + 1);
+    &x[..len]
+}
+
+fn cmp_mag(a: &[u32], b: &[u32]) -> Ordering {
+    a.len()
+        .cmp(&b.len())
+        .then_with(|| a.iter().rev().cmp(b.iter().rev()))
+}
+
+/// Adds `x` shifted up by `shift` limbs into `acc`.
+fn add_at(acc: &mut Vec<u32>, x: &[u32], shift: usize) {
+    if acc.len() < shift + x.len() {
+        acc.resize(shift + x.len(), 0);
+    }
+    let mut carry = 0u64;
+    for (i, &d) in x.iter().enumerate() {
+        let t = acc[shift + i] as u64 + d as u64 + carry;
+        acc[shift + i] = t as u32;
+        carry = t >> 32;
+    }
+    let mut i = shift + x.len();
+    while carry > 0 {
+        if i == acc.len() {
+            acc.push(0);
+        }
+        let t = acc[i] as u64 + carry;
+        acc[i] = t as u32;
+        carry = t >> 32;
+        i += 1;
+    }
+}
+
+/// Subtracts `x` from `acc`, which must be at least `x`.
+fn sub_from(acc: &mut Vec<u32>, x: &[u32]) {
+    let mut borrow = false;
+    for (i, d) in acc.iter_mut().enumerate() {
+        if i >= x.len() && !borrow {
+            break;
+        }
+        let (t, b1) = d.overflowing_sub(x.get(i).copied().unwrap_or(0));
+        let (t, b2) = t.overflowing_sub(borrow as u32);
+        *d = t;
+        borrow = b1 || b2;
+    }
+    debug_assert!(!borrow, "magnitude underflow");
+    trim(acc);
+}
+
+fn add_mag(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let (a, b) = if a.len() >= b.len() { (a, b) } else { (b, a) };
+    let mut result = a.to_vec();
+    add_at(&mut result, b, 0);
+    result
+}
+
+fn sub_mag(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut result = a.to_vec();
+    sub_from(&mut result, b);
+    result
+}
+
+fn mul_mag(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let (a, b) = if a.len() >= b.len() { (a, b) } else { (b, a) };
+    let mut result = if b.is_empty() {
+        Vec::new()
+    } else if b.len() < KARATSUBA_THRESHOLD {
+        mul_schoolbook(a, b)
+    } else if b.len() < NTT_THRESHOLD || a.len() + b.len() > NTT_MAX_LIMBS {
+        mul_karatsuba(a, b)
+    } else {
+        mul_ntt(a, b)
+    };
+    trim(&mut result);
+    result
+}
+
+fn mul_schoolbook(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut result = vec![0u32; a.len() + b.len()];
+    for (i, &y) in b.iter().enumerate() {
+        let mut carry = 0u64;
+        for (j, &x) in a.iter().enumerate() {
+            let t = result[i + j] as u64 + x as u64 * y as u64 + carry;
+            result[i + j] = t as u32;
+            carry = t >> 32;
+        }
+        result[i + a.len()] = carry as u32;
+    }
+    result
+}
+
+/// Multiplies with `a.len() >= b.len()`.
+fn mul_karatsuba(a: &[u32], b: &[u32]) -> Vec<u32> {
+    // Unbalanced factors are cut into pieces of the shorter length.
+    if a.len() >= 2 * b.len() {
+        let mut result = Vec::new();
+        for (i, chunk) in a.chunks(b.len()).enumerate() {
+            add_at(&mut result, &mul_mag(trimmed(chunk), b), i * b.len());
+        }
+        return result;
+    }
+
+    // (a1 B^k + a0)(b1 B^k + b0) with the middle term from one product.
+    let k = a.len() / 2;
+    let (a0, a1) = (trimmed(&a[..k]), &a[k..]);
+    let (b0, b1) = (trimmed(&b[..k]), &b[k..]);
+    let z0 = mul_mag(a0, b0);
+    let z2 = mul_mag(a1, b1);
+    let mut z1 = mul_mag(&add_mag(a0, a1), &add_mag(b0, b1));
+    sub_from(&mut z1, &z0);
+    sub_from(&mut z1, &z2);
+
+    let mut result = z0;
+    add_at(&mut result, &z1, k);
+    add_at(&mut result, &z2, 2 * k);
+    result
+}
+
+/// Multiplies through an exact convolution of 16-bit digits.
+fn mul_ntt(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let split = |x: &[u32]| -> Vec<u64> {
+        x.iter()
+            .flat_map(|&d| [(d & 0xffff) as u64, (d >> 16) as u64])
+            .collect()
+    };
+    let c = convolution_u64(&split(a), &split(b));
+
+    let mut digits = Vec::with_capacity(c.len() + 4);
+    let mut carry = 0u128;
+    for x in c {
+        carry += x as u128;
+        digits.push((carry & 0xffff) as u32);
+        carry >>= 16;
+    }
+    while carry > 0 {
+        digits.push((carry & 0xffff) as u32);
+        carry >>= 16;
+    }
+
+    digits
+        .chunks(2)
+        .map(|pair| pair[0] | pair.get(1).map_or(0, |&hi| hi << 16))
+        .collect()
+}
+
+/// Returns `x << bits` for `bits < 32`.
+fn shl_bits(x: &[u32], bits: u32) -> Vec<u32> {
+    if bits == 0 {
+        return x.to_vec();
+    }
+    let mut result = Vec::with_capacity(x.len() + 1);
+    let mut carry = 0;
+    for &d in x {
+        result.push(d << bits | carry);
+        carry = d >> (32 - bits);
+    }
+    result.push(carry);
+    result
+}
+
+/// Returns `x >> bits` for `bits < 32`.
+fn shr_bits(x: &[u32], bits: u32) -> Vec<u32> {
+    if bits == 0 {
+        return x.to_vec();
+    }
+    let mut result = vec![0u32; x.len()];
+    for i in 0..x.len() {
+        let hi = x.get(i + 1).map_or(0, |&d| d << (32 - bits));
+        result[i] = x[i] >> bits | hi;
+    }
+    trim(&mut result);
+    result
+}
+
+fn divmod_mag(a: &[u32], b: &[u32]) -> (Vec<u32>, Vec<u32>) {
+    if cmp_mag(a, b) == Ordering::Less {
+        return (Vec::new(), a.to_vec());
+    }
+    if b.len() == 1 {
+        let (q, r) = divmod_small(a, b[0]);
+        return (q, if r == 0 { Vec::new() } else { vec![r] });
+    }
+    if b.len() >= NEWTON_THRESHOLD && a.len() - b.len() >= NEWTON_THRESHOLD {
+        divmod_newton(a, b, &reciprocal(b))
+    } else {
+        divmod_knuth(a, b)
+    }
+}
+
+fn divmod_small(a: &[u32], d: u32) -> (Vec<u32>, u32) {
+    let mut q = vec![0u32; a.len()];
+    let mut rem = 0u64;
+    for i in (0..a.len()).rev() {
+        let cur = rem << 32 | a[i] as u64;
+        q[i] = (cur / d as u64) as u32;
+        rem = cur % d as u64;
+    }
+    trim(&mut q);
+    (q, rem as u32)
+}
+
+/// Knuth's algorithm D for `a >= b` with at least two limbs in `b`.
+fn divmod_knuth(a: &[u32], b: &[u32]) -> (Vec<u32>, Vec<u32>) {
+    // Normalize so that the top limb of b has its high bit set.
+    let shift = b[b.len() - 1].leading_zeros();
+    let mut b = shl_bits(b, shift);
+    trim(&mut b);
+    let mut a = shl_bits(a, shift);
+    if shift == 0 {
+        a.push(0);
+    }
+
+    let n = b.len();
+    let m = a.len() - n;
+    let mut q = vec![0u32; m];
+    let (top, second) = (b[n - 1] as u64, b[n - 2] as u64);
+    for j in (0..m).rev() {
+        // Estimate from the top two limbs, then fix with the third.
+        let num = (a[j + n] as u64) << 32 | a[j + n - 1] as u64;
+        let mut qhat = num / top;
+        let mut rhat = num % top;
+        while qhat >> 32 != 0 || qhat * second > (rhat << 32 | a[j + n - 2] as u64) {
+            qhat -= 1;
+            rhat += top;
+            if rhat >> 32 != 0 {
+                break;
+            }
+        }
+
+        let mut borrow = 0i64;
+        let mut carry = 0u64;
+        for i in 0..n {
+            let p = qhat * b[i] as u64 + carry;
+            carry = p >> 32;
+            let t = a[i + j] as i64 - borrow - (p & 0xffff_ffff) as i64;
+            a[i + j] = t as u32;
+            borrow = (t < 0) as i64;
+        }
+        let t = a[j + n] as i64 - borrow - carry as i64;
+        a[j + n] = t as u32;
+
+        // The estimate was one too large at most once in a while: add back.
+        if t < 0 {
+            qhat -= 1;
+            let mut carry = 0u64;
+            for i in 0..n {
+                let s = a[i + j] as u64 + b[i] as u64 + carry;
+                a[i + j] = s as u32;
+                carry = s >> 32;
+            }
+            a[j + n] = a[j + n].wrapping_add(carry as u32);
+        }
+        q[j] = qhat as u32;
+    }
+
+    trim(&mut q);
+    (q, shr_bits(trimmed(&a[..n]), shift))
+}
+
+/// Returns `floor(B^(2m) / b)` for `b` of `m` limbs, where `B = 2^32`.
+fn reciprocal(b: &[u32]) -> Vec<u32> {
+    let m = b.len();
+    let mut power = vec![0u32; 2 * m];
+    power.push(1);
+    if m < NEWTON_THRESHOLD {
+        return divmod_knuth(&power, b).0;
+    }
+
+    // The reciprocal y of the top h limbs gives x = y B^(m-h), good to about
+    // h limbs; one Newton step x + x (B^(2m) - b x) / B^(2m) doubles that.
+    // Every product is taken with y and shifted, skipping the zero limbs of x.
+    let h = m.div_ceil(2) + 2;
+    let shift = m - h;
+    let y = reciprocal(&b[shift..]);
+    let by = mul_mag(b, &y);
+    let mut top = vec![0u32; m + h];
+    top.push(1);
+    let mut x = vec![0u32; shift];
+    x.extend(&y);
+    let mut bx = vec![0u32; shift];
+    bx.extend(&by);
+    if cmp_mag(&by, &top) == Ordering::Greater {
+        let e = sub_mag(&by, &top);
+        let step = mul_mag(&y, &e);
+        let delta = step.get(2 * h..).unwrap_or(&[]);
+        sub_from(&mut x, delta);
+        sub_from(&mut bx, &mul_mag(b, delta));
+    } else {
+        let e = sub_mag(&top, &by);
+        let step = mul_mag(&y, &e);
+        let delta = step.get(2 * h..).unwrap_or(&[]);
+        add_at(&mut x, delta, 0);
+        add_at(&mut bx, &mul_mag(b, delta), 0);
+    }
+    trim(&mut x);
+    trim(&mut bx);
+
+    // The estimate is off by a few units at most.
+    while cmp_mag(&bx, &power) == Ordering::Greater {
+        sub_from(&mut x, &[1]);
+        sub_from(&mut bx, b);
+    }
+    let mut r = sub_mag(&power, &bx);
+    while cmp_mag(&r, b) != Ordering::Less {
+        add_at(&mut x, &[1], 0);
+        sub_from(&mut r, b);
+    }
+    x
+}
+
+/// Divides block by block, `m` limbs at a time, given `inv = reciprocal(b)`.
+fn divmod_newton(a: &[u32], b: &[u32], inv: &[u32]) -> (Vec<u32>, Vec<u32>) {
+    let m = b.len();
+    let blocks = a.len().div_ceil(m);
+    let mut q = vec![0u32; blocks * m];
+    let mut rem: Vec<u32> = Vec::new();
+    for block in (0..blocks).rev() {
+        // cur = rem B^m + block < b B^m <= B^(2m)
+        let lo = block * m;
+        let mut cur = a[lo..a.len().min(lo + m)].to_vec();
+        add_at(&mut cur, &rem, m);
+        trim(&mut cur);
+
+        // Only the top m + 1 limbs of cur matter: the estimate
+        // floor(floor(cur / B^(m-1)) inv / B^(m+1)) undershoots by at most three.
+        let product = mul_mag(cur.get(m - 1..).unwrap_or(&[]), inv);
+        let mut qb = product.get(m + 1..).unwrap_or(&[]).to_vec();
+        let mut r = sub_mag(&cur, &mul_mag(b, &qb));
+        while cmp_mag(&r, b) != Ordering::Less {
+            add_at(&mut qb, &[1], 0);
+            sub_from(&mut r, b);
+        }
+        q[lo..lo + qb.len()].copy_from_slice(&qb);
+        rem = r;
+    }
+
+    trim(&mut q);
+    (q, rem)
+}
+
+/// Returns `10^(9 2^i)` for `i < levels`.
+fn chunk_powers(levels: usize) -> Vec<Vec<u32>> {
+    let mut powers: Vec<Vec<u32>> = Vec::with_capacity(levels);
+    for _ in 0..levels {
+        let next = match powers.last() {
+            None => vec![CHUNK],
+            Some(p) => mul_mag(p, p),
+        };
+        powers.push(next);
+    }
+    powers
+}
+
+/// Appends exactly `2^level` base-`10^9` chunks of `x < 10^(9 2^level)`,
+/// least significant first. `divisors[i]` holds `10^(9 2^i)` and, when long
+/// enough for Newton division, its reciprocal, shared by all the blocks.
+fn to_chunks(x: &[u32], level: usize, divisors: &[(Vec<u32>, Vec<u32>)], out: &mut Vec<u32>) {
+    if 1 << level <= CHUNK_LEAF {
+        let mut x = x.to_vec();
+        for _ in 0..1 << level {
+            let (q, r) = divmod_small(&x, CHUNK);
+            out.push(r);
+            x = q;
+        }
+        return;
+    }
+    let (power, inv) = &divisors[level - 1];
+    let (hi, lo) = if inv.is_empty() {
+        divmod_mag(x, power)
+    } else {
+        divmod_newton(x, power, inv)
+    };
+    to_chunks(&lo, level - 1, divisors, out);
+    to_chunks(&hi, level - 1, divisors, out);
+}
+
+/// Returns the value of base-`10^9` chunks, least significant first, with
+/// `chunks.len() <= 2^level`.
+fn from_chunks(chunks: &[u32], level: usize, powers: &[Vec<u32>]) -> Vec<u32> {
+    if chunks.len() <= CHUNK_LEAF {
+        let mut x: Vec<u32> = Vec::new();
+        for &c in chunks.iter().rev() {
+            let mut carry = c as u64;
+            for d in x.iter_mut() {
+                let t = *d as u64 * CHUNK as u64 + carry;
+                *d = t as u32;
+                carry = t >> 32;
+            }
+            if carry > 0 {
+                x.push(carry as u32);
+            }
+        }
+        return x;
+    }
+    let half = 1 << (level - 1);
+    if chunks.len() <= half {
+        return from_chunks(chunks, level - 1, powers);
+    }
+    let lo = from_chunks(&chunks[..half], level - 1, powers);
+    let hi = from_chunks(&chunks[half..], level - 1, powers);
+    let mut result = mul_mag(&hi, &powers[level - 1]);
+    add_at(&mut result, &lo, 0);
+    result
+}
+
+/// Error returned when parsing a [`BigInt`] from a string fails.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseBigIntError;
+
+impl Display for ParseBigIntError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid digit found in string")
+    }
+}
+
+impl std::error::Error for ParseBigIntError {}
+
+/// Parses an optionally signed decimal integer of any length.
+impl FromStr for BigInt {
+    type Err = ParseBigIntError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (negative, digits) = match s.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, s.strip_prefix('+').unwrap_or(s)),
+        };
+        if digits.is_empty() || !digits.bytes().all(|c| c.is_ascii_digit()) {
+            return Err(ParseBigIntError);
+        }
+
+        let chunks: Vec<u32> = digits
+            .as_bytes()
+            .rchunks(CHUNK_DIGITS)
+            .map(|chunk| chunk.iter().fold(0, |acc, &c| acc * 10 + (c - b'0') as u32))
+            .collect();
+        let level = chunks.len().next_power_of_two().trailing_zeros() as usize;
+        let powers = chunk_powers(level);
+
+        Ok(Self::from_parts(
+            negative,
+            from_chunks(&chunks, level, &powers),
+        ))
+    }
+}
+
+impl Display for BigInt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_zero() {
+            return f.pad_integral(true, "", "0");
+        }
+
+        // Find the level with the value below 10^(9 2^level); that power
+        // itself is needed only if its length does not settle it.
+        let mut powers = chunk_powers(1);
+        let level = loop {
+            let last = &powers[powers.len() - 1];
+            if cmp_mag(&self.mag, last) == Ordering::Less {
+                break powers.len() - 1;
+            }
+            if 2 * last.len() - 1 > self.mag.len() {
+                break powers.len();
+            }
+            let next = mul_mag(last, last);
+            powers.push(next);
+        };
+        let divisors: Vec<(Vec<u32>, Vec<u32>)> = powers
+            .into_iter()
+            .take(level)
+            .map(|p| {
+                let inv = if p.len() >= NEWTON_THRESHOLD {
+                    reciprocal(&p)
+                } else {
+                    Vec::new()
+                };
+                (p, inv)
+            })
+            .collect();
+        let mut chunks = Vec::with_capacity(1 << level);
+        to_chunks(&self.mag, level, &divisors, &mut chunks);
+        trim(&mut chunks);
+
+        let mut digits = chunks[chunks.len() - 1].to_string();
+        for c in chunks.iter().rev().skip(1) {
+            digits.push_str(&format!("{:09}", c));
+        }
+        f.pad_integral(!self.negative, "", &digits)
+    }
+}
+
+impl Debug for BigInt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Display::fmt(self, f)
+    }
+}
+
+impl Integer for BigInt {
+    fn checked_add(&self, rhs: &Self) -> Option<Self> {
+        Some(self + rhs)
+    }
+
+    fn checked_sub(&self, rhs: &Self) -> Option<Self> {
+        Some(self - rhs)
+    }
+
+    fn checked_mul(&self, rhs: &Self) -> Option<Self> {
+        Some(self * rhs)
+    }
+
+    fn checked_neg(&self) -> Option<Self> {
+        Some(-self)
+    }
+
+    fn bits(&self) -> u64 {
+        BigInt::bits(self)
+    }
+
+    fn shr_abs(&self, shift: u64) -> Self {
+        let limbs = usize::try_from(shift / 32).unwrap_or(usize::MAX);
+        if limbs >= self.mag.len() {
+            return Self::zero();
+        }
+        let mag = shr_bits(&self.mag[limbs..], (shift % 32) as u32);
+        Self::from_parts(self.negative, mag)
+    }
+
+    fn to_f64(&self) -> f64 {
+        BigInt::to_f64(self)
+    }
+}
+
+impl Ord for BigInt {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.negative, other.negative) {
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+            (false, false) => cmp_mag(&self.mag, &other.mag),
+            (true, true) => cmp_mag(&other.mag, &self.mag),
+        }
+    }
+}
+
+impl PartialOrd for BigInt {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+macro_rules! impl_from_unsigned {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for BigInt {
+                fn from(x: $t) -> Self {
+                    let x = x as u128;
+                    let mag = (0..4).map(|i| (x >> (32 * i)) as u32).collect();
+                    Self::from_parts(false, mag)
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! impl_from_signed {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for BigInt {
+                fn from(x: $t) -> Self {
+                    let mut result = Self::from(x.unsigned_abs());
+                    result.negative = x < 0;
+                    result
+                }
+            }
+        )*
+    };
+}
+
+impl_from_unsigned!(u8, u16, u32, u64, u128, usize);
+impl_from_signed!(i8, i16, i32, i64, i128, isize);
+
+impl Neg for BigInt {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::from_parts(!self.negative, self.mag)
+    }
+}
+
+impl Neg for &BigInt {
+    type Output = BigInt;
+
+    fn neg(self) -> BigInt {
+        -self.clone()
+    }
+}
+
+impl AddAssign<&Self> for BigInt {
+    fn add_assign(&mut self, rhs: &Self) {
+        *self = self.add_signed(rhs, false);
+    }
+}
+
+impl SubAssign<&Self> for BigInt {
+    fn sub_assign(&mut self, rhs: &Self) {
+        *self = self.add_signed(rhs, true);
+    }
+}
+
+impl MulAssign<&Self> for BigInt {
+    fn mul_assign(&mut self, rhs: &Self) {
+        *self = Self::from_parts(self.negative != rhs.negative, mul_mag(&self.mag, &rhs.mag));
+    }
+}
+
+impl DivAssign<&Self> for BigInt {
+    fn div_assign(&mut self, rhs: &Self) {
+        *self = self.div_rem(rhs).0;
+    }
+}
+
+impl RemAssign<&Self> for BigInt {
+    fn rem_assign(&mut self, rhs: &Self) {
+        *self = self.div_rem(rhs).1;
+    }
+}
+
+macro_rules! impl_bigint_ops {
+    ($($trait:ident $method:ident $assign:ident $assign_method:ident),*) => {
+        $(
+            impl $assign for BigInt {
+                fn $assign_method(&mut self, rhs: Self) {
+                    $assign::$assign_method(self, &rhs);
+                }
+            }
+
+            impl $trait<&Self> for BigInt {
+                type Output = Self;
+
+                fn $method(mut self, rhs: &Self) -> Self {
+                    $assign::$assign_method(&mut self, rhs);
+                    self
+                }
+            }
+
+            impl $trait for BigInt {
+                type Output = Self;
+
+                fn $method(self, rhs: Self) -> Self {
+                    self.$method(&rhs)
+                }
+            }
+
+            impl $trait for &BigInt {
+                type Output = BigInt;
+
+                fn $method(self, rhs: Self) -> BigInt {
+                    self.clone().$method(rhs)
+                }
+            }
+        )*
+    };
+}
+
+impl_bigint_ops!(
+    Add add AddAssign add_assign,
+    Sub sub SubAssign sub_assign,
+    Mul mul MulAssign mul_assign,
+    Div div DivAssign div_assign,
+    Rem rem RemAssign rem_assign
+);
+
+impl Sum for BigInt {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::zero(), |acc, x| acc + x)
+    }
+}
+
+impl<'a> Sum<&'a Self> for BigInt {
+    fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.fold(Self::zero(), |acc, x| acc + x)
+    }
+}
+
+impl Product for BigInt {
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::one(), |acc, x| acc * x)
+    }
+}
+
+impl<'a> Product<&'a Self> for BigInt {
+    fn product<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.fold(Self::one(), |acc, x| acc * x)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::Rng;
+
+    fn random_mag(rng: &mut Rng, len: usize) -> Vec<u32> {
+        let mut mag: Vec<u32> = (0..len).map(|_| rng.next() as u32).collect();
+        // Runs of all-ones and zeros stress the carries and qhat corrections.
+        if rng.next().is_multiple_of(4) {
+            for d in mag.iter_mut().skip(len / 3) {
+                *d = if rng.next().is_multiple_of(2) {
+                    u32::MAX
+                } else {
+                    0
+                };
+            }
+        }
+        if let Some(top) = mag.last_mut() {
+            *top |= 1;
+        }
+        mag
+    }
+
+    fn random_int(rng: &mut Rng, len: usize) -> BigInt {
+        BigInt::from_parts(rng.next().is_multiple_of(2), random_mag(rng, len))
+    }
+
+    /// Decimal digits by repeated short division, for reference.
+    fn naive_decimal(x: &BigInt) -> String {
+        let mut mag = x.mag.clone();
+        let mut digits = Vec::new();
+        while !mag.is_empty() {
+            let (q, r) = divmod_small(&mag, 10);
+            digits.push(b'0' + r as u8);
+            mag = q;
+        }
+        if digits.is_empty() {
+            digits.push(b'0');
+        }
+        if x.negative {
+            digits.push(b'-');
+        }
+        digits.reverse();
+        String::from_utf8(digits).unwrap()
+    }
+
+    #[test]
+    fn test_small_against_i128() {
+        let mut rng = Rng(90);
+        let values: Vec<i128> = (0..60)
+            .map(|i| match i % 4 {
+                0 => (rng.next() % 100) as i128 - 50,
+                1 => rng.next() as i64 as i128,
+                2 => ((rng.next() as i128) << 40) - (rng.next() as i128),
+                _ => [0, 1, -1, i64::MIN as i128, u64::MAX as i128][i % 5],
+            })
+            .collect();
+        for &a in &values {
+            for &b in &values {
+                let (x, y) = (BigInt::from(a), BigInt::from(b));
+                assert_eq!(x.cmp(&y), a.cmp(&b));
+                assert_eq!((&x + &y).to_string(), (a + b).to_string());
+                assert_eq!((&x - &y).to_string(), (a - b).to_string());
+                if let Some(p) = a.checked_mul(b) {
+                    assert_eq!((&x * &y).to_string(), p.to_string());
+                }
+                if b != 0 {
+                    assert_eq!((&x / &y).to_string(), (a / b).to_string());
+                    assert_eq!((&x % &y).to_string(), (a % b).to_string());
+                    let (q, r) = x.div_rem_euclid(&y);
+                    assert_eq!(q, BigInt::from(a.div_euclid(b)));
+                    assert_eq!(r, BigInt::from(a.rem_euclid(b)));
+                }
+            }
+        }
+        assert_eq!(BigInt::from(i128::MIN).to_string(), i128::MIN.to_string());
+        assert_eq!(BigInt::from(u128::MAX).to_string(), u128::MAX.to_string());
+    }
+
+    #[test]
+    fn test_mul_algorithms_agree() {
+        let mut rng = Rng(91);
+        for &(n, m) in &[
+            (40, 33),
+            (100, 90),
+            (300, 35),
+            (700, 600),
+            (1500, 520),
+            (2000, 2000),
+        ] {
+            let a = random_mag(&mut rng, n);
+            let b = random_mag(&mut rng, m);
+            let mut expected = mul_schoolbook(&a, &b);
+            trim(&mut expected);
+            let mut karatsuba = mul_karatsuba(&a, &b);
+            trim(&mut karatsuba);
+            let mut ntt = mul_ntt(&a, &b);
+            trim(&mut ntt);
+            assert_eq!(karatsuba, expected, "{}x{}", n, m);
+            assert_eq!(ntt, expected, "{}x{}", n, m);
+            assert_eq!(mul_mag(&a, &b), expected, "{}x{}", n, m);
+        }
+
+        let max = vec![u32::MAX; 1200];
+        let mut expected = mul_schoolbook(&max, &max);
+        trim(&mut expected);
+        assert_eq!(mul_mag(&max, &max), expected);
+    }
+
+    #[test]
+    fn test_div_rem() {
+        let mut rng = Rng(92);
+        for &(n, m) in &[
+            (1, 1),
+            (5, 1),
+            (5, 2),
+            (20, 19),
+            (60, 30),
+            (300, 150),
+            (500, 130),
+            (1000, 300),
+            (2000, 999),
+            (129, 128),
+        ] {
+            for _ in 0..3 {
+                let a = random_int(&mut rng, n);
+                let b = random_int(&mut rng, m);
+                let (q, r) = a.div_rem(&b);
+                assert_eq!(&q * &b + &r, a);
+                assert!(r.abs() < b.abs());
+                assert!(r.is_zero() || r.negative == a.negative);
+
+                // Knuth and Newton division agree where both apply.
+                if m >= 2 && n >= m {
+                    let knuth = divmod_knuth(&a.mag, &b.mag);
+                    assert_eq!(knuth, (q.mag.clone(), r.mag.clone()));
+                }
+            }
+        }
+
+        // Exact multiples and the all-ones corner case.
+        let b = BigInt::from_parts(false, vec![u32::MAX; 200]);
+        let q = BigInt::from_parts(false, vec![u32::MAX; 300]);
+        let a = &b * &q;
+        assert_eq!(a.div_rem(&b), (q.clone(), BigInt::zero()));
+        assert_eq!((&a - &BigInt::one()).div_rem(&b).0, &q - &BigInt::one());
+    }
+
+    #[test]
+    fn test_decimal_conversions() {
+        let mut rng = Rng(93);
+        for len in [0, 1, 2, 15, 16, 17, 50, 200, 1000, 3000] {
+            let x = random_int(&mut rng, len);
+            let s = x.to_string();
+            assert_eq!(s, naive_decimal(&x));
+            assert_eq!(s.parse::<BigInt>(), Ok(x));
+        }
+
+        let digits: String = (0..20_000)
+            .map(|i| (b'1' + (i * 7 % 9) as u8) as char)
+            .collect();
+        let x: BigInt = digits.parse().unwrap();
+        assert_eq!(x.to_string(), digits);
+
+        let ten = BigInt::from(10);
+        assert_eq!(ten.pow(1000).to_string(), format!("1{}", "0".repeat(1000)));
+        assert_eq!("-000123".parse(), Ok(BigInt::from(-123)));
+        assert_eq!("-0".parse::<BigInt>().unwrap().signum(), 0);
+        assert_eq!("+7".parse(), Ok(BigInt::from(7)));
+        for bad in ["", "-", "+", "1_000", "12a", " 1", "--1"] {
+            assert_eq!(bad.parse::<BigInt>(), Err(ParseBigIntError), "{:?}", bad);
+        }
+        assert_eq!(format!("{:>6}", BigInt::from(-42)), "   -42");
+        assert_eq!(format!("{:+}", BigInt::from(42)), "+42");
+    }
+
+    #[test]
+    fn test_pow_and_gcd() {
+        let mut x = BigInt::one();
+        for e in 0..200u32 {
+            assert_eq!(BigInt::from(-3).pow(e), x);
+            x *= &BigInt::from(-3);
+        }
+        assert_eq!(BigInt::zero().pow(0), BigInt::one());
+
+        let fact: BigInt = (1..=100).map(BigInt::from).product();
+        assert_eq!(fact.to_string().len(), 158);
+        let fib = |n: usize| {
+            let (mut a, mut b) = (BigInt::zero(), BigInt::one());
+            for _ in 0..n {
+                let c = &a + &b;
+                a = std::mem::replace(&mut b, c);
+            }
+            a
+        };
+        // gcd(F_m, F_n) = F_gcd(m, n)
+        assert_eq!(fib(600).gcd(&-fib(450)), fib(150));
+        assert_eq!(BigInt::from(12).bits(), 4);
+    }
+
+    #[test]
+    fn test_to_f64() {
+        let mut rng = Rng(91);
+        for _ in 0..1_000 {
+            let x = (rng.next() as i128) << (rng.next() % 64) ^ rng.next() as i128;
+            assert_eq!(BigInt::from(x).to_f64(), x as f64);
+        }
+
+        // 2^200 + 2^147 is a tie rounded to even; one more bit rounds up.
+        let p = |e: u32| BigInt::from(2).pow(e);
+        assert_eq!((p(200) + p(147)).to_f64(), 2f64.powi(200));
+        assert_eq!(
+            (p(200) + p(147) + BigInt::one()).to_f64(),
+            2f64.powi(200) + 2f64.powi(148)
+        );
+        assert_eq!((-p(1023) * BigInt::from(3)).to_f64(), f64::NEG_INFINITY);
+        assert_eq!((p(1024) - BigInt::one()).to_f64(), f64::INFINITY);
+        assert_eq!((p(1024) - p(971)).to_f64(), f64::MAX);
+    }
+}
@@ -0,0 +1,90 @@
+//! The signed integer interface shared by the primitive types and [`BigInt`].
+//!
+//! [`BigInt`]: crate::num::BigInt
+
+use std::fmt::{Debug, Display};
+use std::ops::{Add, Div, Mul, Neg, Rem, Sub};
+
+/// Signed integers with overflow-checked arithmetic.
+///
+/// The plain operators are used only where they cannot overflow, such as
+/// division by a positive divisor; everything else goes through the checked
+/// methods. [`BigInt`](crate::num::BigInt) never overflows.
+pub trait Integer:
+    Clone
+    + Ord
+    + Debug
+    + Display
+    + From<i8>
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Rem<Output = Self>
+    + Neg<Output = Self>
+{
+    /// Returns `self + rhs`, or `None` on overflow.
+    fn checked_add(&self, rhs: &Self) -> Option<Self>;
+
+    /// Returns `self - rhs`, or `None` on overflow.
+    fn checked_sub(&self, rhs: &Self) -> Option<Self>;
+
+    /// Returns `self * rhs`, or `None` on overflow.
+    fn checked_mul(&self, rhs: &Self) -> Option<Self>;
+
+    /// Returns `-self`, or `None` on overflow.
+    fn checked_neg(&self) -> Option<Self>;
+
+    /// Returns the number of bits in `|self|`.
+    fn bits(&self) -> u64;
+
+    /// Returns `|self| >> shift` with the sign of `self`.
+    fn shr_abs(&self, shift: u64) -> Self;
+
+    /// Returns the nearest `f64`.
+    fn to_f64(&self) -> f64;
+}
+
+macro_rules! impl_integer {
+    ($($t:ty),*) => {
+        $(
+            impl Integer for $t {
+                fn checked_add(&self, rhs: &Self) -> Option<Self> {
+                    <$t>::checked_add(*self, *rhs)
+                }
+
+                fn checked_sub(&self, rhs: &Self) -> Option<Self> {
+                    <$t>::checked_sub(*self, *rhs)
+                }
+
+                fn checked_mul(&self, rhs: &Self) -> Option<Self> {
+                    <$t>::checked_mul(*self, *rhs)
+                }
+
+                fn checked_neg(&self) -> Option<Self> {
+                    <$t>::checked_neg(*self)
+                }
+
+                fn bits(&self) -> u64 {
+                    (<$t>::BITS - self.unsigned_abs().leading_zeros()) as u64
+                }
+
+                fn shr_abs(&self, shift: u64) -> Self {
+                    let shift = u32::try_from(shift).unwrap_or(u32::MAX);
+                    let m = self.unsigned_abs().checked_shr(shift).unwrap_or(0) as $t;
+                    if *self < 0 {
+                        m.wrapping_neg()
+                    } else {
+                        m
+                    }
+                }
+
+                fn to_f64(&self) -> f64 {
+                    *self as f64
+                }
+            }
+        )*
+    };
+}
+
+impl_integer!(i32, i64, i128, isize);
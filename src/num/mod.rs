@@ -0,0 +1,9 @@
+//! Exact numeric types.
+
+mod bigint;
+mod integer;
+mod rational;
+
+pub use self::bigint::{BigInt, ParseBigIntError};
+pub use self::integer::Integer;
+pub use self::rational::Rational;
@@ -0,0 +1,513 @@
+//! Exact rational numbers over any [`Integer`].
+//!
+//! Values are kept in lowest terms with a positive denominator, so equality
+//! is structural. Products divide out common factors before multiplying and
+//! comparison walks continued fractions instead of cross-multiplying, so
+//! neither overflows unless the result itself does. Sums divide out the gcd
+//! of the numerators and that of the denominators first, but can still
+//! overflow in an intermediate product when a further common factor only
+//! cancels at the end.
+
+use crate::num::integer::Integer;
+use std::cmp::Ordering;
+use std::fmt::{self, Debug, Display};
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+/// A fraction `num / den` in lowest terms with `den > 0`.
+///
+/// The operators panic on overflow; the `checked_*` methods return `None`
+/// instead.
+///
+/// # Examples
+///
+/// ```
+/// use algorithm_rs::num::Rational;
+///
+/// let a = Rational::new(1i64, 6);
+/// let b = Rational::new(-3, 4);
+/// assert_eq!(a + b, Rational::new(-7, 12));
+/// assert!(b < a);
+/// assert_eq!((a / b).to_string(), "-2/9");
+///
+/// let big = Rational::new(i64::MAX, 2);
+/// assert_eq!(big.checked_add(&big), Some(Rational::from(i64::MAX)));
+/// assert_eq!(big.checked_add(&Rational::one()), None);
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rational<T> {
+    num: T,
+    den: T,
+}
+
+/// Returns a gcd of `a` and `b`, up to sign, without negating anything.
+fn gcd_signed<T: Integer>(a: &T, b: &T) -> T {
+    let (zero, minus_one) = (T::from(0), T::from(-1));
+    let (mut a, mut b) = (a.clone(), b.clone());
+    while b != zero {
+        // `MIN % -1` overflows even though the remainder is zero.
+        let r = if b == minus_one {
+            zero.clone()
+        } else {
+            a % b.clone()
+        };
+        a = std::mem::replace(&mut b, r);
+    }
+    a
+}
+
+/// Returns the non-negative gcd of `a` and `b > 0`.
+fn gcd<T: Integer>(a: &T, b: &T) -> T {
+    let g = gcd_signed(a, b);
+    // |g| <= b, so negating cannot overflow.
+    if g < T::from(0) {
+        -g
+    } else {
+        g
+    }
+}
+
+/// Returns `(floor(a / b), a mod b)` for `b > 0`.
+fn floor_div<T: Integer>(a: &T, b: &T) -> (T, T) {
+    let zero = T::from(0);
+    let (q, r) = (a.clone() / b.clone(), a.clone() % b.clone());
+    if r < zero {
+        (q - T::from(1), r + b.clone())
+    } else {
+        (q, r)
+    }
+}
+
+/// Compares `a / b` with `c / d` for `b, d > 0` by their continued fractions.
+fn cmp_fractions<T: Integer>(a: &T, b: &T, c: &T, d: &T) -> Ordering {
+    let zero = T::from(0);
+    let (q1, r1) = floor_div(a, b);
+    let (q2, r2) = floor_div(c, d);
+    if q1 != q2 {
+        return q1.cmp(&q2);
+    }
+    match (r1 == zero, r2 == zero) {
+        (true, true) => Ordering::Equal,
+        (true, false) => Ordering::Less,
+        (false, true) => Ordering::Greater,
+        // r1 / b < r2 / d  <=>  d / r2 < b / r1
+        (false, false) => cmp_fractions(d, &r2, b, &r1),
+    }
+}
+
+impl<T: Integer> Rational<T> {
+    /// Returns `num / den` in lowest terms.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `den` is zero or the normalized value overflows `T`.
+    pub fn new(num: T, den: T) -> Self {
+        assert!(den != T::from(0), "denominator must be nonzero");
+        Self::checked_new(num, den).expect("rational overflow")
+    }
+
+    /// Returns `num / den` in lowest terms, or `None` if `den` is zero or
+    /// the normalized value overflows, as `i64::MIN / -1` does.
+    pub fn checked_new(num: T, den: T) -> Option<Self> {
+        let zero = T::from(0);
+        if den == zero {
+            return None;
+        }
+
+        // Dividing by a negative gcd other than the minimum could overflow.
+        let mut g = gcd_signed(&num, &den);
+        if g < zero {
+            if let Some(neg) = g.checked_neg() {
+                g = neg;
+            }
+        }
+        let (mut num, mut den) = (num / g.clone(), den / g);
+        if den < zero {
+            num = num.checked_neg()?;
+            den = den.checked_neg()?;
+        }
+        Some(Self { num, den })
+    }
+
+    /// Returns the integer `n` as a fraction.
+    pub fn from_integer(n: T) -> Self {
+        Self {
+            num: n,
+            den: T::from(1),
+        }
+    }
+
+    /// Returns zero.
+    pub fn zero() -> Self {
+        Self::from_integer(T::from(0))
+    }
+
+    /// Returns one.
+    pub fn one() -> Self {
+        Self::from_integer(T::from(1))
+    }
+
+    /// Returns the numerator, which carries the sign.
+    pub fn numer(&self) -> &T {
+        &self.num
+    }
+
+    /// Returns the denominator, which is always positive.
+    pub fn denom(&self) -> &T {
+        &self.den
+    }
+
+    /// Returns `true` if the value is zero.
+    pub fn is_zero(&self) -> bool {
+        self.num == T::from(0)
+    }
+
+    /// Returns `true` if the value is an integer.
+    pub fn is_integer(&self) -> bool {
+        self.den == T::from(1)
+    }
+
+    /// Returns the largest integer not above the value.
+    pub fn floor(&self) -> T {
+        floor_div(&self.num, &self.den).0
+    }
+
+    /// Returns the smallest integer not below the value.
+    pub fn ceil(&self) -> T {
+        let (q, r) = floor_div(&self.num, &self.den);
+        if r == T::from(0) {
+            q
+        } else {
+            q + T::from(1)
+        }
+    }
+
+    /// Returns the nearest `f64`, within a few units in the last place.
+    pub fn to_f64(&self) -> f64 {
+        // Drop low bits so that both parts stay finite as floats.
+        let shift = self.num.bits().max(self.den.bits()).saturating_sub(1000);
+        if shift == 0 {
+            self.num.to_f64() / self.den.to_f64()
+        } else {
+            self.num.shr_abs(shift).to_f64() / self.den.shr_abs(shift).to_f64()
+        }
+    }
+
+    /// Returns `-self`, or `None` on overflow.
+    pub fn checked_neg(&self) -> Option<Self> {
+        Some(Self {
+            num: self.num.checked_neg()?,
+            den: self.den.clone(),
+        })
+    }
+
+    /// Returns the absolute value, or `None` on overflow.
+    pub fn checked_abs(&self) -> Option<Self> {
+        if self.num < T::from(0) {
+            self.checked_neg()
+        } else {
+            Some(self.clone())
+        }
+    }
+
+    /// Returns `1 / self`, or `None` if `self` is zero or on overflow.
+    pub fn checked_recip(&self) -> Option<Self> {
+        Self::checked_new(self.den.clone(), self.num.clone())
+    }
+
+    /// Returns `self + rhs`, or `None` on overflow.
+    pub fn checked_add(&self, rhs: &Self) -> Option<Self> {
+        self.combine(rhs, T::checked_add)
+    }
+
+    /// Returns `self - rhs`, or `None` on overflow.
+    pub fn checked_sub(&self, rhs: &Self) -> Option<Self> {
+        self.combine(rhs, T::checked_sub)
+    }
+
+    /// Applies `op` to the numerators over a common denominator.
+    fn combine(&self, rhs: &Self, op: fn(&T, &T) -> Option<T>) -> Option<Self> {
+        // a/b +- c/d = h (a/h (d/g) +- c/h (b/g)) / (b d / g) with h the gcd
+        // of the numerators, which is coprime to b d, and g that of the
+        // denominators, whose factors are the only ones the bracket can share.
+        // A zero or unnegatable h is replaced by 1.
+        let (zero, one) = (T::from(0), T::from(1));
+        let h = gcd_signed(&self.num, &rhs.num);
+        let h = if h > zero {
+            h
+        } else {
+            h.checked_neg().filter(|h| *h > zero).unwrap_or(one)
+        };
+        let g = gcd(&self.den, &rhs.den);
+        let (b, d) = (self.den.clone() / g.clone(), rhs.den.clone() / g.clone());
+        let (a, c) = (self.num.clone() / h.clone(), rhs.num.clone() / h.clone());
+        let sum = op(&a.checked_mul(&d)?, &c.checked_mul(&b)?)?;
+        let g2 = gcd(&sum, &g);
+        let num = (sum / g2.clone()).checked_mul(&h)?;
+        let den = (self.den.clone() / g2).checked_mul(&d)?;
+        Some(Self { num, den })
+    }
+
+    /// Returns `self * rhs`, or `None` on overflow.
+    pub fn checked_mul(&self, rhs: &Self) -> Option<Self> {
+        if self.is_zero() || rhs.is_zero() {
+            return Some(Self::zero());
+        }
+        let g1 = gcd(&self.num, &rhs.den);
+        let g2 = gcd(&rhs.num, &self.den);
+        let num = (self.num.clone() / g1.clone()).checked_mul(&(rhs.num.clone() / g2.clone()))?;
+        let den = (self.den.clone() / g2).checked_mul(&(rhs.den.clone() / g1))?;
+        Some(Self { num, den })
+    }
+
+    /// Returns `self / rhs`, or `None` if `rhs` is zero or on overflow.
+    pub fn checked_div(&self, rhs: &Self) -> Option<Self> {
+        self.checked_mul(&rhs.checked_recip()?)
+    }
+}
+
+impl<T: Integer> From<T> for Rational<T> {
+    fn from(n: T) -> Self {
+        Self::from_integer(n)
+    }
+}
+
+impl<T: Integer> Default for Rational<T> {
+    fn default() -> Self {
+        Self::zero()
+    }
+}
+
+impl<T: Integer> Ord for Rational<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        cmp_fractions(&self.num, &self.den, &other.num, &other.den)
+    }
+}
+
+impl<T: Integer> PartialOrd for Rational<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: Integer> Display for Rational<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_integer() {
+            write!(f, "{}", self.num)
+        } else {
+            write!(f, "{}/{}", self.num, self.den)
+        }
+    }
+}
+
+impl<T: Integer> Debug for Rational<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Display::fmt(self, f)
+    }
+}
+
+impl<T: Integer> Neg for Rational<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        self.checked_neg().expect("rational overflow")
+    }
+}
+
+impl<T: Integer> Neg for &Rational<T> {
+    type Output = Rational<T>;
+
+    fn neg(self) -> Rational<T> {
+        self.checked_neg().expect("rational overflow")
+    }
+}
+
+macro_rules! impl_rational_ops {
+    ($($trait:ident $method:ident $assign:ident $assign_method:ident $checked:ident),*) => {
+        $(
+            impl<T: Integer> $assign<&Self> for Rational<T> {
+                fn $assign_method(&mut self, rhs: &Self) {
+                    *self = self.$checked(rhs).expect("rational overflow");
+                }
+            }
+
+            impl<T: Integer> $assign for Rational<T> {
+                fn $assign_method(&mut self, rhs: Self) {
+                    $assign::$assign_method(self, &rhs);
+                }
+            }
+
+            impl<T: Integer> $trait<&Self> for Rational<T> {
+                type Output = Self;
+
+                fn $method(mut self, rhs: &Self) -> Self {
+                    $assign::$assign_method(&mut self, rhs);
+                    self
+                }
+            }
+
+            impl<T: Integer> $trait for Rational<T> {
+                type Output = Self;
+
+                fn $method(self, rhs: Self) -> Self {
+                    self.$method(&rhs)
+                }
+            }
+
+            impl<T: Integer> $trait for &Rational<T> {
+                type Output = Rational<T>;
+
+                fn $method(self, rhs: Self) -> Rational<T> {
+                    self.$checked(rhs).expect("rational overflow")
+                }
+            }
+        )*
+    };
+}
+
+impl_rational_ops!(
+    Add add AddAssign add_assign checked_add,
+    Sub sub SubAssign sub_assign checked_sub,
+    Mul mul MulAssign mul_assign checked_mul,
+    Div div DivAssign div_assign checked_div
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::num::BigInt;
+    use crate::test_util::Rng;
+
+    fn small(rng: &mut Rng) -> i64 {
+        (rng.next() % 41) as i64 - 20
+    }
+
+    fn gcd_i128(a: i128, b: i128) -> i128 {
+        if b == 0 {
+            a.abs()
+        } else {
+            gcd_i128(b, a % b)
+        }
+    }
+
+    /// Checks that `r` is the reduced form of `n / d`.
+    fn assert_reduced(r: &Rational<i64>, n: i128, d: i128) {
+        let g = gcd_i128(n, d) * d.signum();
+        assert_eq!((*r.numer() as i128, *r.denom() as i128), (n / g, d / g));
+    }
+
+    #[test]
+    fn test_arithmetic_against_cross_multiplication() {
+        let mut rng = Rng(220);
+        for _ in 0..5_000 {
+            let (a, b) = (small(&mut rng), small(&mut rng));
+            let (c, d) = (small(&mut rng), small(&mut rng));
+            if b == 0 || d == 0 {
+                assert_eq!(Rational::checked_new(a, 0), None);
+                continue;
+            }
+            let (x, y) = (Rational::new(a, b), Rational::new(c, d));
+            assert_reduced(&x, a as i128, b as i128);
+
+            let (a, b, c, d) = (a as i128, b as i128, c as i128, d as i128);
+            assert_reduced(&(x + y), a * d + b * c, b * d);
+            assert_reduced(&(x - y), a * d - b * c, b * d);
+            assert_reduced(&(x * y), a * c, b * d);
+            if c != 0 {
+                assert_reduced(&(x / y), a * d, b * c);
+            } else {
+                assert_eq!(x.checked_div(&y), None);
+            }
+            assert_eq!(
+                x.cmp(&y),
+                (a * d * b.signum() * d.signum()).cmp(&(b * c * b.signum() * d.signum()))
+            );
+            assert_eq!(x.floor() as i128, (a * b.signum()).div_euclid(b.abs()));
+            assert_eq!(x.ceil() as i128, -(-a * b.signum()).div_euclid(b.abs()));
+        }
+    }
+
+    #[test]
+    fn test_overflow_detection() {
+        let min = Rational::from(i64::MIN);
+        let max = Rational::from(i64::MAX);
+        assert_eq!(Rational::checked_new(i64::MIN, -1), None);
+        assert_eq!(Rational::new(i64::MIN, i64::MIN), Rational::one());
+        assert_eq!(Rational::new(i64::MIN, -2), Rational::from(1 << 62));
+        assert_eq!(Rational::checked_new(1, i64::MIN), None);
+        assert_eq!(Rational::checked_new(-1, i64::MIN), None);
+        assert_eq!(min.checked_neg(), None);
+        assert_eq!(min.checked_recip(), None);
+        assert_eq!(max.checked_add(&Rational::one()), None);
+        assert_eq!(min.checked_sub(&Rational::one()), None);
+        assert_eq!(max.checked_mul(&Rational::from(2)), None);
+        assert_eq!(min.checked_add(&max), Some(Rational::from(-1)));
+        assert_eq!(Rational::from(-1).checked_sub(&min), Some(max));
+
+        // Common factors are removed before anything is multiplied.
+        let x = Rational::new(i64::MAX, 6);
+        let y = Rational::new(5, i64::MAX);
+        assert_eq!(x * y, Rational::new(5, 6));
+        let p = Rational::new(1i64, 1 << 61);
+        assert_eq!(p + p, Rational::new(1, 1 << 60));
+        let (q, r) = (Rational::new(1i64, 3 << 60), Rational::new(1, 5 << 60));
+        assert_eq!(q + r, Rational::new(1, 15 << 57));
+        let (q, r) = (Rational::new(1, i64::MAX), Rational::new(1, i64::MAX - 1));
+        assert_eq!(q.checked_add(&r), None);
+        let (q, r) = (Rational::new(i64::MAX, 2), Rational::new(-i64::MAX, 3));
+        assert_eq!(q.checked_add(&r), Some(Rational::new(i64::MAX, 6)));
+        assert_eq!(q.checked_sub(&-r), Some(Rational::new(i64::MAX, 6)));
+        assert_eq!(min.checked_add(&min), None);
+        assert_eq!(min.checked_sub(&min), Some(Rational::zero()));
+    }
+
+    #[test]
+    fn test_ordering_near_limits() {
+        let big = i64::MAX;
+        let mut values = vec![
+            Rational::new(big - 1, big),
+            Rational::new(big - 2, big - 1),
+            Rational::new(1, big),
+            Rational::new(1, big - 1),
+            Rational::new(-1, big),
+            Rational::from(i64::MIN),
+            Rational::from(big),
+            Rational::new(big, big - 1),
+            Rational::zero(),
+        ];
+        values.sort();
+        let expected: Vec<Rational<i128>> = values
+            .iter()
+            .map(|r| Rational::new(*r.numer() as i128, *r.denom() as i128))
+            .collect();
+        for w in expected.windows(2) {
+            let lhs = *w[0].numer() * *w[1].denom();
+            let rhs = *w[1].numer() * *w[0].denom();
+            assert!(lhs < rhs, "{:?} < {:?}", w[0], w[1]);
+        }
+    }
+
+    #[test]
+    fn test_bigint_and_to_f64() {
+        let mut h = Rational::<BigInt>::zero();
+        for i in 1..=100 {
+            h += Rational::new(BigInt::one(), BigInt::from(i));
+        }
+        assert_eq!(
+            h.denom().to_string(),
+            "2788815009188499086581352357412492142272"
+        );
+        assert!((h.to_f64() - 5.187377517639621).abs() < 1e-12);
+        assert!((Rational::new(1i64, 3).to_f64() - 1.0 / 3.0).abs() < 1e-16);
+
+        // Both parts far beyond the range of f64.
+        let huge = BigInt::from(7).pow(2_000);
+        let r = Rational::new(huge.clone() * BigInt::from(2), huge * BigInt::from(-3));
+        assert_eq!(r, Rational::new(BigInt::from(-2), BigInt::from(3)));
+        let r = Rational::new(
+            BigInt::from(3).pow(1_500) + BigInt::one(),
+            BigInt::from(3).pow(1_500) * BigInt::from(4),
+        );
+        assert!((r.to_f64() - 0.25).abs() < 1e-15);
+        assert!(Rational::new(BigInt::from(-1), BigInt::from(3)) < Rational::zero());
+    }
+}
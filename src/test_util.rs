@@ -0,0 +1,19 @@
+//! Helpers shared by the unit tests.
+
+/// A xorshift generator, so randomized tests are reproducible from a seed.
+pub(crate) struct Rng(pub(crate) u64);
+
+impl Rng {
+    pub(crate) fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    /// Returns a random bit. Xorshift is linear over GF(2), so its raw bits
+    /// would only ever fill matrices of rank at most 64.
+    pub(crate) fn bit(&mut self) -> bool {
+        self.next().wrapping_mul(0x9e37_79b9_7f4a_7c15) >> 63 == 1
+    }
+}